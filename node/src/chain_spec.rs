@@ -113,5 +113,9 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			"key": Some(root_key),
 		},
+		"poeModule": {
+			// No claims exist at genesis, submit them with `create_claim`.
+			"claims": Vec::<(Vec<u8>, AccountId)>::new(),
+		},
 	})
 }
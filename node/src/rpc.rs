@@ -7,12 +7,20 @@
 
 use std::sync::Arc;
 
-use jsonrpsee::RpcModule;
-use node_template_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+	RpcModule,
+};
+use node_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Nonce};
+use pallet_coinflip_runtime_api::{CoinFlipApi as CoinFlipRuntimeApi, PlayerStats};
+use pallet_poe_runtime_api::{ClaimDetails, PoeApi as PoeRuntimeApi};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::Bytes;
 
 pub use sc_rpc_api::DenyUnsafe;
 
@@ -26,6 +34,119 @@ pub struct FullDeps<C, P> {
 	pub deny_unsafe: DenyUnsafe,
 }
 
+/// Queries of the claims notarized by the proof of existence pallet.
+#[rpc(server)]
+pub trait PoeApi {
+	/// The claims owned by `account`.
+	#[method(name = "poe_claimsOf")]
+	fn claims_of(&self, account: AccountId, at: Option<Hash>) -> RpcResult<Vec<Bytes>>;
+
+	/// The details of `claim`, if it exists.
+	#[method(name = "poe_claim")]
+	fn claim(
+		&self,
+		claim: Bytes,
+		at: Option<Hash>,
+	) -> RpcResult<Option<ClaimDetails<AccountId, BlockNumber>>>;
+
+	/// The latest owners of `claim` with the block at which each got it, oldest first.
+	#[method(name = "poe_claimHistory")]
+	fn claim_history(
+		&self,
+		claim: Bytes,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<(AccountId, BlockNumber)>>;
+}
+
+/// Queries of the player records kept by the coin flip game pallet.
+#[rpc(server)]
+pub trait CoinFlipApi {
+	/// The record of `account`, all zeros if it never played.
+	#[method(name = "coinflip_stats")]
+	fn stats(&self, account: AccountId, at: Option<Hash>) -> RpcResult<PlayerStats<Balance>>;
+
+	/// The players who won the most, with their total winnings, best first.
+	#[method(name = "coinflip_leaderboard")]
+	fn leaderboard(&self, at: Option<Hash>) -> RpcResult<Vec<(AccountId, Balance)>>;
+}
+
+/// Error code of a failed call into the runtime.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: sp_api::ApiError) -> ErrorObjectOwned {
+	ErrorObject::owned(RUNTIME_ERROR, "Unable to query the runtime", Some(error.to_string()))
+}
+
+/// The `poe_*` RPC methods, answered by the `PoeApi` of the runtime.
+pub struct Poe<C> {
+	client: Arc<C>,
+}
+
+impl<C> Poe<C> {
+	/// Create the `poe_*` RPC methods on top of `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> PoeApiServer for Poe<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber>,
+{
+	fn claims_of(&self, account: AccountId, at: Option<Hash>) -> RpcResult<Vec<Bytes>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let claims = self.client.runtime_api().claims_of(at, account).map_err(runtime_error)?;
+		Ok(claims.into_iter().map(Bytes).collect())
+	}
+
+	fn claim(
+		&self,
+		claim: Bytes,
+		at: Option<Hash>,
+	) -> RpcResult<Option<ClaimDetails<AccountId, BlockNumber>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().claim(at, claim.0).map_err(runtime_error)
+	}
+
+	fn claim_history(
+		&self,
+		claim: Bytes,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<(AccountId, BlockNumber)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().claim_history(at, claim.0).map_err(runtime_error)
+	}
+}
+
+/// The `coinflip_*` RPC methods, answered by the `CoinFlipApi` of the runtime.
+pub struct CoinFlip<C> {
+	client: Arc<C>,
+}
+
+impl<C> CoinFlip<C> {
+	/// Create the `coinflip_*` RPC methods on top of `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> CoinFlipApiServer for CoinFlip<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CoinFlipRuntimeApi<Block, AccountId, Balance>,
+{
+	fn stats(&self, account: AccountId, at: Option<Hash>) -> RpcResult<PlayerStats<Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().stats(at, account).map_err(runtime_error)
+	}
+
+	fn leaderboard(&self, at: Option<Hash>) -> RpcResult<Vec<(AccountId, Balance)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().leaderboard(at).map_err(runtime_error)
+	}
+}
+
 /// Instantiate all full RPC extensions.
 pub fn create_full<C, P>(
 	deps: FullDeps<C, P>,
@@ -37,6 +158,8 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber>,
+	C::Api: CoinFlipRuntimeApi<Block, AccountId, Balance>,
 	P: TransactionPool + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
@@ -46,7 +169,9 @@ where
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Poe::new(client.clone()).into_rpc())?;
+	module.merge(CoinFlip::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
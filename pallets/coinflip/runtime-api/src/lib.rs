@@ -0,0 +1,39 @@
+//! Runtime API definition of the coin flip game pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// The record of a player, as returned by [`CoinFlipApi::stats`].
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PlayerStats<Balance> {
+	/// The number of plays decided, revealed or slashed.
+	pub plays: u32,
+	/// The number of plays won.
+	pub wins: u32,
+	/// The sum of the stakes of all plays.
+	pub total_wagered: Balance,
+	/// The sum of the pots won.
+	pub total_won: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries of the player records kept by the coin flip game pallet.
+	pub trait CoinFlipApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The record of `account`, all zeros if it never played.
+		fn stats(account: AccountId) -> PlayerStats<Balance>;
+
+		/// The players who won the most, with their total winnings, best first.
+		///
+		/// Only the best players are kept on chain, up to the runtime's `LeaderboardSize`.
+		fn leaderboard() -> Vec<(AccountId, Balance)>;
+	}
+}
@@ -0,0 +1,135 @@
+use crate::*;
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	traits::{Currency, Get, Hooks},
+	BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Permill,
+};
+use sp_std::vec::Vec;
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+// Open a table owned by `owner`, with a house edge so that the owner is paid on every play.
+fn create_table<T: Config>(owner: &T::AccountId) -> TableId {
+	let fee = T::Currency::minimum_balance();
+	T::Currency::make_free_balance_be(owner, fee * 10u32.into());
+	Pallet::<T>::create_table(
+		RawOrigin::Signed(owner.clone()).into(),
+		fee,
+		Permill::from_percent(5),
+		fee * 1_000u32.into(),
+	)
+	.expect("table is created");
+	Pallet::<T>::next_table_id() - 1
+}
+
+// Commit `who` to a secret at `table` in the current block.
+fn commit<T: Config>(table: TableId, who: &T::AccountId, secret: &Secret) {
+	let fee = T::Currency::minimum_balance();
+	// Enough to pay and stay alive, whatever the outcome.
+	T::Currency::make_free_balance_be(who, fee * 10u32.into());
+	let commitment = Pallet::<T>::commitment_of(who, secret);
+	Pallet::<T>::play(RawOrigin::Signed(who.clone()).into(), table, commitment)
+		.expect("commitment is accepted");
+}
+
+benchmarks! {
+	create_table {
+		let caller: T::AccountId = whitelisted_caller();
+		let fee = T::Currency::minimum_balance();
+		T::Currency::make_free_balance_be(&caller, fee * 10u32.into());
+		let house_edge = Permill::from_percent(5);
+		let max_pot = fee * 1_000u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), fee, house_edge, max_pot)
+	verify {
+		assert_last_event::<T>(
+			Event::TableCreated { table: 0, owner: caller, fee, house_edge, max_pot }.into()
+		)
+	}
+
+	close_table {
+		let caller: T::AccountId = whitelisted_caller();
+		let table = create_table::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), table)
+	verify {
+		assert!(Tables::<T>::get(table).is_none());
+	}
+
+	play {
+		let table = create_table::<T>(&account("owner", 0, SEED));
+		let caller: T::AccountId = whitelisted_caller();
+		// Enough to pay and stay alive, whatever the outcome.
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 10u32.into());
+		let commitment = Pallet::<T>::commitment_of(&caller, &[0u8; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), table, commitment)
+	verify {
+		assert_last_event::<T>(Event::Committed { table, player: caller, commitment }.into())
+	}
+
+	reveal {
+		let table = create_table::<T>(&account("owner", 0, SEED));
+		let caller: T::AccountId = whitelisted_caller();
+		commit::<T>(table, &caller, &[0u8; 32]);
+		// A win updates a full leaderboard.
+		let board = (0 .. T::LeaderboardSize::get())
+			.map(|i| (account("winner", i, SEED), Zero::zero()))
+			.collect::<Vec<_>>();
+		Leaderboard::<T>::put(BoundedVec::truncate_from(board));
+		let opened_at =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::RevealDelay::get());
+		frame_system::Pallet::<T>::set_block_number(opened_at);
+		Pallet::<T>::on_initialize(opened_at);
+	}: _(RawOrigin::Signed(caller.clone()), table, [0u8; 32])
+	verify {
+		assert!(Commitments::<T>::get(table, &caller).is_none());
+		assert_eq!(Nonce::<T>::get(), 1);
+		assert_eq!(PlayerStats::<T>::get(&caller).plays, 1);
+	}
+
+	on_initialize {
+		// Plays slashed as their reveal period is over, each at its own table.
+		let n in 0 .. T::MaxCommitsPerBlock::get();
+		// Plays whose reveal period opens, each at its own table, so that a seed is drawn.
+		let t in 1 .. T::MaxCommitsPerBlock::get();
+		for i in 0 .. n {
+			let table = create_table::<T>(&account("owner", i, SEED));
+			commit::<T>(table, &account("player", i, SEED), &[0u8; 32]);
+		}
+		let committed_at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::RevealPeriod::get());
+		frame_system::Pallet::<T>::set_block_number(committed_at);
+		for i in 0 .. t {
+			let table = create_table::<T>(&account("opener", i, SEED));
+			commit::<T>(table, &account("late player", i, SEED), &[0u8; 32]);
+		}
+		let now: BlockNumberFor<T> = committed_at.saturating_add(T::RevealDelay::get());
+		frame_system::Pallet::<T>::set_block_number(now);
+	}: {
+		Pallet::<T>::on_initialize(now);
+	}
+	verify {
+		assert_eq!(Commitments::<T>::iter().count(), t as usize);
+		assert!(RevealSeeds::<T>::contains_key(now));
+	}
+
+	set_loss_limit {
+		let caller: T::AccountId = whitelisted_caller();
+		let limit = T::Currency::minimum_balance();
+		LossLimits::<T>::insert(&caller, LossLimit { limit, pending: None });
+		// Raising the limit is the slowest path.
+		let higher = limit * 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), Some(higher))
+	verify {
+		assert!(LossLimits::<T>::get(&caller).and_then(|limit| limit.pending).is_some());
+	}
+
+	impl_benchmark_test_suite!(CoinFlipModule, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,8 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 /// A FRAME pallet for coin flip game
-
+///
+/// Any account can open a table with its own entry fee, house edge and maximum pot, and close
+/// it to take back the pot once no play is pending. The pots of all tables are held by the
+/// pallet account, and a share of every stake goes to the treasury.
+///
+/// A play is decided by commit-reveal: the player commits to the hash of a secret with its
+/// stake, then reveals the secret in a later block. When the reveal period of a play opens, a
+/// seed is drawn from the parent block randomness and the secrets revealed by earlier players.
+/// The outcome only depends on that seed and the secret, so neither the player, by picking the
+/// reveal block, nor the block author alone can bias it. Stakes of commitments not revealed in
+/// time are slashed into the pot.
 pub use pallet::*;
+pub use weights::WeightInfo;
 
 #[cfg(test)]
 mod mock;
@@ -10,125 +21,722 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+	use super::*;
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::{
+			BalanceStatus, Currency, ExistenceRequirement, OnUnbalanced, Randomness,
+			ReservableCurrency,
+		},
+		PalletId,
+	};
 	use frame_system::pallet_prelude::*;
-	use frame_support::traits::{Currency, WithdrawReasons, ExistenceRequirement, Randomness};
-	use sp_runtime::traits::{Zero, Hash, Saturating};
+	use sp_runtime::{
+		traits::{AccountIdConversion, Hash, Saturating, Zero},
+		Permill,
+	};
 
-	// TODO: refactor to inject currency trait
-	#[pallet::config]
-	pub trait Config: frame_system::Config + pallet_balances::Config {
-		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+	/// The balance type of the currency used for payments.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// The identifier of a table.
+	pub type TableId = u32;
+
+	/// The secret a player commits to and later reveals.
+	pub type Secret = [u8; 32];
+
+	/// A table players can play at.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Table<AccountId, Balance> {
+		/// The account that opened the table, and receives the house edge.
+		pub owner: AccountId,
+		/// The stake of every play.
+		pub fee: Balance,
+		/// The share of every stake paid to the owner.
+		pub house_edge: Permill,
+		/// The most the pot can hold, the rest of a stake goes to the owner.
+		pub max_pot: Balance,
+		/// The amount paid out to the next winner.
+		pub pot: Balance,
+		/// The number of commitments not yet revealed or slashed.
+		pub pending: u32,
+	}
+
+	pub type TableOf<T> = Table<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// A pending play, waiting for the player to reveal its secret.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Commitment<Hash, Balance, BlockNumber> {
+		/// The hash of the player and its secret.
+		pub hash: Hash,
+		/// The stake reserved from the player.
+		pub stake: Balance,
+		/// The block of the commitment.
+		pub committed_at: BlockNumber,
+	}
+
+	pub type CommitmentOf<T> =
+		Commitment<<T as frame_system::Config>::Hash, BalanceOf<T>, BlockNumberFor<T>>;
+
+	/// The record of a player over all tables.
+	#[derive(
+		Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct Stats<Balance> {
+		/// The number of plays decided, revealed or slashed.
+		pub plays: u32,
+		/// The number of plays won.
+		pub wins: u32,
+		/// The sum of the stakes of all plays.
+		pub total_wagered: Balance,
+		/// The sum of the pots won.
+		pub total_won: Balance,
+	}
+
+	/// The plays of an account in the current rate limiting period.
+	#[derive(
+		Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct PlayWindow<BlockNumber> {
+		/// The block at which the period started.
+		pub start: BlockNumber,
+		/// The number of plays in the period.
+		pub plays: u32,
+	}
+
+	/// The net losses of an account in the current loss limit period.
+	#[derive(
+		Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct LossWindow<Balance, BlockNumber> {
+		/// The block at which the period started.
+		pub start: BlockNumber,
+		/// The stakes of the plays in the period, pending ones included, less the winnings.
+		pub lost: Balance,
+	}
+
+	/// The loss limit an account imposed on itself.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct LossLimit<Balance, BlockNumber> {
+		/// The limit in force.
+		pub limit: Balance,
+		/// A higher limit, or no limit at all, taking over at a block.
+		pub pending: Option<(Option<Balance>, BlockNumber)>,
+	}
+
+	pub type LossLimitOf<T> = LossLimit<BalanceOf<T>, BlockNumberFor<T>>;
 
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The source of randomness mixed into the outcome of a play.
+		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+		/// The currency in which payments are made and the pot is paid out.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The runtime event
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The number of blocks after a commitment before its secret can be revealed.
+		#[pallet::constant]
+		type RevealDelay: Get<BlockNumberFor<Self>>;
+		/// The number of blocks during which a secret can be revealed.
+		#[pallet::constant]
+		type RevealPeriod: Get<BlockNumberFor<Self>>;
+		/// The maximum number of commitments made in a single block.
+		#[pallet::constant]
+		type MaxCommitsPerBlock: Get<u32>;
+		/// The pallet id, used for deriving the account holding the pots.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The share of every stake paid to the treasury.
+		#[pallet::constant]
+		type TreasuryCut: Get<Permill>;
+		/// Handler for the treasury cut, e.g. depositing it into the treasury account.
+		type Treasury: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// The number of players on the leaderboard.
+		#[pallet::constant]
+		type LeaderboardSize: Get<u32>;
+		/// The maximum number of plays of an account in a `PlayPeriod`.
+		#[pallet::constant]
+		type MaxPlaysPerPeriod: Get<u32>;
+		/// The number of blocks over which plays are rate limited.
+		#[pallet::constant]
+		type PlayPeriod: Get<BlockNumberFor<Self>>;
+		/// The number of blocks over which losses are limited, e.g. a day.
+		#[pallet::constant]
+		type LossLimitPeriod: Get<BlockNumberFor<Self>>;
+		/// The number of blocks before raising or removing a loss limit takes effect.
+		#[pallet::constant]
+		type LossLimitDelay: Get<BlockNumberFor<Self>>;
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
-	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
+	/// The open tables.
 	#[pallet::storage]
-	#[pallet::getter(fn payment)]
-	pub type Payment<T: Config> = StorageValue<_, T::Balance>;
+	#[pallet::getter(fn tables)]
+	pub type Tables<T: Config> = StorageMap<_, Twox64Concat, TableId, TableOf<T>>;
 
+	/// The identifier of the next table.
 	#[pallet::storage]
-	#[pallet::getter(fn pot)]
-	pub type Pot<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+	#[pallet::getter(fn next_table_id)]
+	pub type NextTableId<T> = StorageValue<_, TableId, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn nonce)]
 	pub type Nonce<T> = StorageValue<_, u64, ValueQuery>;
 
+	/// The pending commitment of each player at each table.
+	#[pallet::storage]
+	#[pallet::getter(fn commitments)]
+	pub type Commitments<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TableId, Blake2_128Concat, T::AccountId, CommitmentOf<T>>;
+
+	/// The plays whose commitment is slashed at a block if still not revealed.
+	#[pallet::storage]
+	pub type RevealDeadlines<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(TableId, T::AccountId), T::MaxCommitsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The record of each player.
+	#[pallet::storage]
+	#[pallet::getter(fn stats)]
+	pub type PlayerStats<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Stats<BalanceOf<T>>, ValueQuery>;
+
+	/// The players who won the most, with their total winnings, best first.
+	#[pallet::storage]
+	#[pallet::getter(fn leaderboard)]
+	pub type Leaderboard<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, BalanceOf<T>), T::LeaderboardSize>, ValueQuery>;
+
+	/// The plays of each account in its current rate limiting period.
+	#[pallet::storage]
+	pub type PlayWindows<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, PlayWindow<BlockNumberFor<T>>, ValueQuery>;
+
+	/// The net losses of each account in its current loss limit period.
+	#[pallet::storage]
+	#[pallet::getter(fn losses)]
+	pub type Losses<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		LossWindow<BalanceOf<T>, BlockNumberFor<T>>,
+		ValueQuery,
+	>;
+
+	/// The loss limit of each account that set one.
+	#[pallet::storage]
+	#[pallet::getter(fn loss_limits)]
+	pub type LossLimits<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, LossLimitOf<T>>;
+
+	/// The secrets revealed so far, folded into a single hash.
+	#[pallet::storage]
+	#[pallet::getter(fn entropy)]
+	pub type Entropy<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
+	/// The seed deciding the plays whose reveal period opened at a block.
+	#[pallet::storage]
+	#[pallet::getter(fn reveal_seeds)]
+	pub type RevealSeeds<T: Config> = StorageMap<_, Twox64Concat, BlockNumberFor<T>, T::Hash>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		#[serde(skip)]
+		_config: sp_std::marker::PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			// Create the pot account, so that it can receive stakes below the existential deposit
+			let account_id = Pallet::<T>::account_id();
+			let min = T::Currency::minimum_balance();
+			if T::Currency::free_balance(&account_id) < min {
+				let _ = T::Currency::make_free_balance_be(&account_id, min);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		PaymentSet(T::Balance),
-		PlayResult(T::AccountId, T::Balance),
+		/// A table was opened, its pot seeded with one fee from the owner.
+		TableCreated {
+			table: TableId,
+			owner: T::AccountId,
+			fee: BalanceOf<T>,
+			house_edge: Permill,
+			max_pot: BalanceOf<T>,
+		},
+		/// A table was closed and its pot paid to the owner.
+		TableClosed { table: TableId, pot: BalanceOf<T> },
+		/// A player committed to a secret, staking the table fee.
+		Committed { table: TableId, player: T::AccountId, commitment: T::Hash },
+		/// A player revealed its secret, winning `winnings` from the pot.
+		PlayResult { table: TableId, player: T::AccountId, winnings: BalanceOf<T> },
+		/// The stake of a commitment not revealed in time was slashed.
+		CommitmentSlashed { table: TableId, player: T::AccountId, stake: BalanceOf<T> },
+		/// An account set its loss limit, `None` for no limit.
+		LossLimitSet { who: T::AccountId, limit: Option<BalanceOf<T>> },
+		/// An account raised or removed its loss limit, taking effect at block `at`.
+		LossLimitScheduled { who: T::AccountId, limit: Option<BalanceOf<T>>, at: BlockNumberFor<T> },
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
-		NonePaymentValue,
+		/// The table does not exist.
+		TableNotExist,
+		/// Only the owner can close the table.
+		NotTableOwner,
+		/// The max pot is lower than the fee.
+		InvalidMaxPot,
+		/// The fee is lower than the existential deposit.
+		FeeTooLow,
+		/// The max pot is zero.
+		ZeroMaxPot,
+		/// The table still has pending commitments.
+		TableBusy,
+		/// The player already has a pending commitment.
+		AlreadyCommitted,
+		/// The player has no pending commitment.
+		NoCommitment,
+		/// The reveal period of the commitment has not started yet.
+		RevealTooEarly,
+		/// The reveal period of the commitment is over.
+		RevealTooLate,
+		/// The secret does not match the commitment.
+		InvalidSecret,
+		/// Too many commitments were already made in this block.
+		TooManyCommitments,
+		/// The account played `MaxPlaysPerPeriod` times in the current period.
+		TooManyPlays,
+		/// Losing the play would exceed the loss limit of the account.
+		LossLimitExceeded,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let plays = RevealDeadlines::<T>::take(now);
+			let count = plays.len() as u32;
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Start the game by initialize the storage items.
-		#[pallet::weight(0)]
-		pub fn set_payment(origin: OriginFor<T>, value: T::Balance) -> DispatchResultWithPostInfo {
-			// Ensure the function call is a signed message (i.e. a transaction)
-			ensure_signed(origin)?;
-
-			// If `payment` is not initialized with some value, set the payment
-			if Self::payment().is_none() {
-				// set input value to the payment
-				<Payment<T>>::put(value);
-				
-				// Initialize jackpot;
-				<Pot<T>>::put(value);
-
-				// Raise an event for the set payment
-				Self::deposit_event(Event::PaymentSet(value));
+			for (table, player) in plays {
+				Self::slash_unrevealed(table, player, now);
 			}
 
-			Ok(().into())
+			// The reveal period of the plays due now is over
+			RevealSeeds::<T>::remove(now.saturating_sub(T::RevealPeriod::get()));
+			// Fix the outcome of the plays whose reveal period opens now
+			let opening =
+				RevealDeadlines::<T>::decode_len(now.saturating_add(T::RevealPeriod::get()))
+					.unwrap_or(0) as u32;
+			if !opening.is_zero() {
+				RevealSeeds::<T>::insert(now, Self::draw_seed());
+			}
+
+			T::WeightInfo::on_initialize(count, opening)
 		}
-		
-		/// This function allow a user to play our coin flip game
-		#[pallet::weight(0)]
-		pub fn play(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+
+		fn integrity_test() {
+			assert!(!T::RevealDelay::get().is_zero(), "`RevealDelay` must not be zero");
+			assert!(!T::RevealPeriod::get().is_zero(), "`RevealPeriod` must not be zero");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Play our coin flip game at `table` by committing to a secret, see
+		/// [`Pallet::commitment_of`].
+		///
+		/// The table fee is reserved until the secret is revealed with `reveal`, between
+		/// `RevealDelay` and `RevealDelay + RevealPeriod` blocks from now. It is slashed if the
+		/// secret is not revealed in time.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::play())]
+		pub fn play(
+			origin: OriginFor<T>,
+			table: TableId,
+			commitment: T::Hash,
+		) -> DispatchResultWithPostInfo {
 			// Ensure that the function call is a signed message (i.e. a transaction)
 			let sender = ensure_signed(origin)?;
 
-			// Ensure the payment storage item has been set
-			let payment = Self::payment().ok_or(Error::<T>::NonePaymentValue)?;
+			let mut info = Self::tables(table).ok_or(Error::<T>::TableNotExist)?;
+			ensure!(!Commitments::<T>::contains_key(table, &sender), Error::<T>::AlreadyCommitted);
 
-			// Read our storage values, and place them in memory variables
-			let mut nonce = Self::nonce();
-			let mut pot = Self::pot();
+			let now = frame_system::Pallet::<T>::block_number();
+			Self::ensure_can_play(&sender, info.fee, now)?;
+			RevealDeadlines::<T>::try_append(Self::reveal_deadline(now), (table, &sender))
+				.map_err(|_| Error::<T>::TooManyCommitments)?;
 
-			// Try to withdraw the payment from the account, making sure that it will not kill the account
-			let _ = <pallet_balances::Pallet<T> as Currency<_>>::withdraw(&sender, payment, WithdrawReasons::RESERVE.into(), ExistenceRequirement::KeepAlive)?;
+			// Stake the fee until the secret is revealed
+			T::Currency::reserve(&sender, info.fee)?;
+			Commitments::<T>::insert(
+				table,
+				&sender,
+				Commitment { hash: commitment, stake: info.fee, committed_at: now },
+			);
+			info.pending.saturating_inc();
+			Tables::<T>::insert(table, info);
+
+			Self::deposit_event(Event::Committed { table, player: sender, commitment });
+
+			Ok(().into())
+		}
+
+		/// Reveal the secret of the pending commitment at `table`, deciding the play.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::reveal())]
+		pub fn reveal(
+			origin: OriginFor<T>,
+			table: TableId,
+			secret: Secret,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let commitment = Self::commitments(table, &sender).ok_or(Error::<T>::NoCommitment)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				now >= commitment.committed_at.saturating_add(T::RevealDelay::get()),
+				Error::<T>::RevealTooEarly
+			);
+			ensure!(
+				now < Self::reveal_deadline(commitment.committed_at),
+				Error::<T>::RevealTooLate
+			);
+			ensure!(
+				commitment.hash == Self::commitment_of(&sender, &secret),
+				Error::<T>::InvalidSecret
+			);
+			let opened_at = commitment.committed_at.saturating_add(T::RevealDelay::get());
+			let seed = Self::reveal_seeds(opened_at).ok_or(Error::<T>::RevealTooEarly)?;
+
+			// A table is not closed while it has pending commitments
+			let mut info = Self::tables(table).ok_or(Error::<T>::TableNotExist)?;
+			Commitments::<T>::remove(table, &sender);
+			info.pending.saturating_dec();
 
 			let mut winnings = Zero::zero();
 
-			// Generate a random seed using randomness_collective_flip pallet
-			let random_seed = T::Randomness::random_seed().0.using_encoded(T::Hashing::hash);
-			let seed_arr = random_seed.as_ref();
-		
-			// as_ref returns an array of u8
-			if seed_arr[seed_arr.len() - 1] < 128 {
-				// If the user won the coin flip, deposit the pot winnings; cannot fail
-				let _ = <pallet_balances::Pallet<T> as Currency<_>>::deposit_into_existing(&sender, pot)
-					.expect("`sender` must exist since a transaction is being make and withdraw will keep alive; qed.");
-				
+			if Self::is_win(&seed, &secret) {
+				// Winnings make up for the losses of the period the play was committed in, not
+				// for those of a period started since
+				Losses::<T>::mutate(&sender, |window| {
+					if window.start <= commitment.committed_at {
+						window.lost = window.lost.saturating_sub(info.pot)
+					}
+				});
+
+				// If the user won the coin flip, transfer the pot winnings
+				T::Currency::transfer(
+					&Self::account_id(),
+					&sender,
+					info.pot,
+					ExistenceRequirement::KeepAlive,
+				)?;
+
 				// Set the winnings
-				winnings = pot;
+				winnings = info.pot;
 
 				// Reduce the pot to zero
-				pot = Zero::zero();
+				info.pot = Zero::zero();
 			}
 
-			// No matter the outcome, increase the pot by the payment amount
-			pot = pot.saturating_add(payment);
-
-			// Increase the nonce
-			nonce = nonce.wrapping_add(1);
+			// No matter the outcome, the stake goes to the pot, the house and the treasury
+			Self::settle_stake(&mut info, &sender, commitment.stake);
+			Self::record_play(&sender, commitment.stake, winnings);
 
 			// Store the updated value for our storage items
-			<Pot<T>>::put(pot);
-			Nonce::<T>::put(nonce);
+			Tables::<T>::insert(table, info);
+			Nonce::<T>::mutate(|nonce| *nonce = nonce.wrapping_add(1));
+			Entropy::<T>::put(T::Hashing::hash_of(&(Self::entropy(), secret)));
 
 			// Raise event for the play result
-			Self::deposit_event(Event::PlayResult(sender, winnings));
+			Self::deposit_event(Event::PlayResult { table, player: sender, winnings });
+
+			Ok(().into())
+		}
+
+		/// Open a table where every play stakes `fee`, of which the `house_edge` share is paid
+		/// to the caller. The pot is seeded with one `fee` transferred from the caller.
+		///
+		/// The fee must be at least the existential deposit, and the non-zero `max_pot` at least
+		/// the fee.
+		///
+		/// If the pallet account does not exist yet, e.g. when the pallet was added by a runtime
+		/// upgrade, the caller also pays the existential deposit to create it.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::create_table())]
+		pub fn create_table(
+			origin: OriginFor<T>,
+			fee: BalanceOf<T>,
+			house_edge: Permill,
+			max_pot: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(fee >= T::Currency::minimum_balance(), Error::<T>::FeeTooLow);
+			ensure!(!max_pot.is_zero(), Error::<T>::ZeroMaxPot);
+			ensure!(max_pot >= fee, Error::<T>::InvalidMaxPot);
+
+			// Seed the pot, so that the first winner wins something, and keep the pallet account
+			// alive so that pots can be paid out in full
+			let account_id = Self::account_id();
+			let missing = T::Currency::minimum_balance()
+				.saturating_sub(T::Currency::free_balance(&account_id));
+			T::Currency::transfer(
+				&owner,
+				&account_id,
+				fee.saturating_add(missing),
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let table = Self::next_table_id();
+			NextTableId::<T>::put(table.saturating_add(1));
+			Tables::<T>::insert(
+				table,
+				Table { owner: owner.clone(), fee, house_edge, max_pot, pot: fee, pending: 0 },
+			);
+
+			Self::deposit_event(Event::TableCreated { table, owner, fee, house_edge, max_pot });
+
+			Ok(().into())
+		}
+
+		/// Close a table without pending commitments, paying its pot to the owner.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::close_table())]
+		pub fn close_table(origin: OriginFor<T>, table: TableId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let info = Self::tables(table).ok_or(Error::<T>::TableNotExist)?;
+			ensure!(info.owner == sender, Error::<T>::NotTableOwner);
+			ensure!(info.pending.is_zero(), Error::<T>::TableBusy);
+
+			Tables::<T>::remove(table);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&info.owner,
+				info.pot,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::TableClosed { table, pot: info.pot });
+
+			Ok(().into())
+		}
+
+		/// Limit the net losses of the caller over a `LossLimitPeriod` to `limit`, or remove the
+		/// limit with `None`.
+		///
+		/// A lower limit takes effect immediately, a higher limit or removing it only after
+		/// `LossLimitDelay` blocks.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_loss_limit())]
+		pub fn set_loss_limit(
+			origin: OriginFor<T>,
+			limit: Option<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			match Self::loss_limit(&who, now) {
+				// Raising the limit, or removing it, is delayed
+				Some(current) if limit.map_or(true, |limit| limit > current) => {
+					let at = now.saturating_add(T::LossLimitDelay::get());
+					LossLimits::<T>::insert(
+						&who,
+						LossLimit { limit: current, pending: Some((limit, at)) },
+					);
+					Self::deposit_event(Event::LossLimitScheduled { who, limit, at });
+				},
+				_ => {
+					match limit {
+						Some(limit) =>
+							LossLimits::<T>::insert(&who, LossLimit { limit, pending: None }),
+						None => LossLimits::<T>::remove(&who),
+					}
+					Self::deposit_event(Event::LossLimitSet { who, limit });
+				},
+			}
 
 			Ok(().into())
 		}
 	}
 
-}
\ No newline at end of file
+	impl<T: Config> Pallet<T> {
+		/// The account holding the pots of all tables.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// The commitment of `player` to `secret`, to be passed to `play`.
+		pub fn commitment_of(player: &T::AccountId, secret: &Secret) -> T::Hash {
+			T::Hashing::hash_of(&(player, secret))
+		}
+
+		/// Whether revealing `secret` wins the pot, given the `seed` drawn when the reveal
+		/// period of the play opened.
+		pub fn is_win(seed: &T::Hash, secret: &Secret) -> bool {
+			let seed = T::Hashing::hash_of(&(secret, seed));
+			let seed_arr = seed.as_ref();
+
+			// as_ref returns an array of u8
+			seed_arr[seed_arr.len() - 1] < 128
+		}
+
+		/// The seed drawn in the current block for the plays whose reveal period opens now.
+		///
+		/// The parent block randomness is mixed with the secrets revealed before.
+		pub fn draw_seed() -> T::Hash {
+			let (random, _) = T::Randomness::random_seed();
+			T::Hashing::hash_of(&(random, Self::entropy()))
+		}
+
+		/// The loss limit of `who` in force at block `now`, `None` if it has none.
+		pub fn loss_limit(who: &T::AccountId, now: BlockNumberFor<T>) -> Option<BalanceOf<T>> {
+			let loss_limit = Self::loss_limits(who)?;
+			match loss_limit.pending {
+				Some((limit, at)) if at <= now => limit,
+				_ => Some(loss_limit.limit),
+			}
+		}
+
+		/// Record a play of `who` staking `stake`, ensuring it is within its rate and loss
+		/// limits.
+		fn ensure_can_play(
+			who: &T::AccountId,
+			stake: BalanceOf<T>,
+			now: BlockNumberFor<T>,
+		) -> DispatchResult {
+			PlayWindows::<T>::try_mutate(who, |window| {
+				if now >= window.start.saturating_add(T::PlayPeriod::get()) {
+					*window = PlayWindow { start: now, plays: 0 };
+				}
+				ensure!(window.plays < T::MaxPlaysPerPeriod::get(), Error::<T>::TooManyPlays);
+				window.plays.saturating_inc();
+				Ok::<_, DispatchError>(())
+			})?;
+
+			let limit = Self::loss_limit(who, now);
+			Losses::<T>::try_mutate(who, |window| {
+				if now >= window.start.saturating_add(T::LossLimitPeriod::get()) {
+					*window = LossWindow { start: now, lost: Zero::zero() };
+				}
+				// Pending plays count as lost
+				let lost = window.lost.saturating_add(stake);
+				ensure!(limit.map_or(true, |limit| lost <= limit), Error::<T>::LossLimitExceeded);
+				window.lost = lost;
+				Ok(())
+			})
+		}
+
+		/// The block at which a commitment made at `committed_at` is slashed if not revealed.
+		fn reveal_deadline(committed_at: BlockNumberFor<T>) -> BlockNumberFor<T> {
+			committed_at
+				.saturating_add(T::RevealDelay::get())
+				.saturating_add(T::RevealPeriod::get())
+		}
+
+		/// Take the reserved `stake` of `player`: the treasury cut goes to the treasury, the
+		/// house edge and whatever exceeds the max pot to the owner, and the rest to the pot of
+		/// `table`.
+		fn settle_stake(table: &mut TableOf<T>, player: &T::AccountId, stake: BalanceOf<T>) {
+			let cut = T::TreasuryCut::get().mul_floor(stake);
+			let edge = table.house_edge.mul_floor(stake);
+			let room = table.max_pot.saturating_sub(table.pot);
+			let to_pot = stake.saturating_sub(cut).saturating_sub(edge).min(room);
+			let to_owner = stake.saturating_sub(cut).saturating_sub(to_pot);
+
+			let not_pot = T::Currency::repatriate_reserved(
+				player,
+				&Self::account_id(),
+				to_pot,
+				BalanceStatus::Free,
+			)
+			.unwrap_or(to_pot);
+			table.pot = table.pot.saturating_add(to_pot.saturating_sub(not_pot));
+
+			let not_owner = T::Currency::repatriate_reserved(
+				player,
+				&table.owner,
+				to_owner,
+				BalanceStatus::Free,
+			)
+			.unwrap_or(to_owner);
+
+			// Whatever could not be moved goes to the treasury as well
+			let (imbalance, _) = T::Currency::slash_reserved(
+				player,
+				cut.saturating_add(not_pot).saturating_add(not_owner),
+			);
+			T::Treasury::on_unbalanced(imbalance);
+		}
+
+		/// Record a decided play of `player` in its stats, and in the leaderboard if it won.
+		fn record_play(player: &T::AccountId, stake: BalanceOf<T>, winnings: BalanceOf<T>) {
+			let stats = PlayerStats::<T>::mutate(player, |stats| {
+				stats.plays.saturating_inc();
+				stats.total_wagered.saturating_accrue(stake);
+				if !winnings.is_zero() {
+					stats.wins.saturating_inc();
+					stats.total_won.saturating_accrue(winnings);
+				}
+				stats.clone()
+			});
+
+			if winnings.is_zero() {
+				return
+			}
+			Leaderboard::<T>::mutate(|board| {
+				board.retain(|(who, _)| who != player);
+				let index =
+					board.iter().position(|(_, won)| *won < stats.total_won).unwrap_or(board.len());
+				// Fails if the board is full of better players
+				let _ = board.force_insert_keep_left(index, (player.clone(), stats.total_won));
+			});
+		}
+
+		/// Slash the stake of `player` at `table` if its commitment is due at `now`.
+		fn slash_unrevealed(table: TableId, player: T::AccountId, now: BlockNumberFor<T>) {
+			let Some(commitment) = Self::commitments(table, &player) else { return };
+			if Self::reveal_deadline(commitment.committed_at) != now {
+				return
+			}
+			let Some(mut info) = Self::tables(table) else { return };
+
+			Commitments::<T>::remove(table, &player);
+			info.pending.saturating_dec();
+			Self::settle_stake(&mut info, &player, commitment.stake);
+			Self::record_play(&player, commitment.stake, Zero::zero());
+			Tables::<T>::insert(table, info);
+
+			Self::deposit_event(Event::CommitmentSlashed {
+				table,
+				player,
+				stake: commitment.stake,
+			});
+		}
+	}
+}
@@ -1,107 +1,125 @@
 // Creating mock runtime here
 
 use crate as pallet_coinflip;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Currency, OnUnbalanced, Randomness},
+	PalletId,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
 use sp_core::H256;
-use frame_support::{parameter_types, traits::Randomness};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, Permill,
 };
-use frame_system as system;
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
-	pub enum Test where
-		Block = Block,
-		NodeBlock = Block,
-		UncheckedExtrinsic = UncheckedExtrinsic,
+	pub enum Test
 	{
-		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
-		CoinFlipModule: pallet_coinflip::{Pallet, Call, Storage, Event<T>},
+		System: frame_system,
+		Balances: pallet_balances,
+		CoinFlipModule: pallet_coinflip,
 	}
 );
 
-parameter_types! {
-	pub const BlockHashCount: u64 = 250;
-	pub const SS58Prefix: u8 = 42;
-}
-
-impl system::Config for Test {
+impl frame_system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
 	type DbWeight = ();
-	type Origin = Origin;
-	type Call = Call;
-	type Index = u64;
-	type BlockNumber = u64;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
-	type Header = Header;
-	type Event = Event;
-	type BlockHashCount = BlockHashCount;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
 	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
-	type SS58Prefix = SS58Prefix;
+	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
-}
-
-parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
-	pub const MaxReserves: u32 = 2;
+	type MaxConsumers = ConstU32<16>;
 }
 
 impl pallet_balances::Config for Test {
-	type Balance = u64;
-	type DustRemoval = ();
-	type Event = Event;
-	type ExistentialDeposit = ExistentialDeposit;
-	type AccountStore = frame_system::Pallet<Test>;
 	type MaxLocks = ();
-	type MaxReserves = MaxReserves;
+	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
 	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
 }
 
-impl Randomness<<Test as system::Config>::Hash, <Test as system::Config>::BlockNumber> for CoinFlipModule {
-	fn random(_subject: &[u8]) -> (<Test as system::Config>::Hash, <Test as system::Config>::BlockNumber) {
-		let output = match Self::nonce()  {
-			0 => H256::from_low_u64_be(101),
-			1 => H256::from_low_u64_be(150),
-			_ => panic!("do not use other value except 1,2 for nonce in test cases"),
-		};
-		(output, 0)
+impl Randomness<H256, BlockNumberFor<Test>> for CoinFlipModule {
+	fn random(_subject: &[u8]) -> (H256, BlockNumberFor<Test>) {
+		// Changes with every play and every block, like the parent block randomness.
+		let block = System::block_number();
+		(H256::from_low_u64_be(Self::nonce().wrapping_mul(1_000) + block), block)
 	}
 }
 
+pub const TREASURY: u64 = 99;
+
+// Deposit the treasury cut into the `TREASURY` account.
+pub struct ToTreasury;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for ToTreasury {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&TREASURY, amount);
+	}
+}
+
+parameter_types! {
+	pub const CoinFlipPalletId: PalletId = PalletId(*b"py/cflip");
+	pub static TreasuryCut: Permill = Permill::zero();
+}
+
 impl pallet_coinflip::Config for Test {
 	type Randomness = CoinFlipModule;
-	type Event = Event;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type RevealDelay = ConstU64<1>;
+	type RevealPeriod = ConstU64<5>;
+	type MaxCommitsPerBlock = ConstU32<2>;
+	type PalletId = CoinFlipPalletId;
+	type TreasuryCut = TreasuryCut;
+	type Treasury = ToTreasury;
+	type LeaderboardSize = ConstU32<2>;
+	type MaxPlaysPerPeriod = ConstU32<3>;
+	type PlayPeriod = ConstU64<10>;
+	type LossLimitPeriod = ConstU64<20>;
+	type LossLimitDelay = ConstU64<5>;
+	type WeightInfo = ();
 }
 
-// pub type System = frame_system::Pallet<Test>;
-// pub type Balances = pallet_balances::Pallet<Test>;
-
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-
-	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![
-			(1, 10),
-			(2, 20),
-		]
-	}.assimilate_storage(&mut t).unwrap();
-	
-	t.into()
+	let mut ext: sp_io::TestExternalities = RuntimeGenesisConfig {
+		system: Default::default(),
+		balances: pallet_balances::GenesisConfig { balances: vec![(1, 10), (2, 20), (3, 30)] },
+		coin_flip_module: Default::default(),
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	// Events are not emitted on the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,80 +1,644 @@
 // Tests to be written here
 
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
-use frame_support::traits::Currency;
-use sp_runtime::traits::BadOrigin;
 use super::*;
+use crate::{mock::*, Error};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, Hooks, ReservableCurrency},
+};
 use pallet_balances::Error as BalancesError;
+use sp_runtime::{traits::BadOrigin, Permill};
+
+// Open a table owned by account 3 and set its pot.
+fn table(fee: u64, house_edge: Permill, max_pot: u64, pot: u64) -> TableId {
+	assert_ok!(CoinFlipModule::create_table(RuntimeOrigin::signed(3), fee, house_edge, max_pot));
+	let table = CoinFlipModule::next_table_id() - 1;
+	set_pot(table, pot);
+	table
+}
+
+// Set the pot of `table`, funding the pallet account accordingly.
+fn set_pot(table: TableId, pot: u64) {
+	let previous = CoinFlipModule::tables(table).unwrap().pot;
+	Tables::<Test>::mutate(table, |info| info.as_mut().unwrap().pot = pot);
+	let account = CoinFlipModule::account_id();
+	Balances::make_free_balance_be(&account, Balances::free_balance(&account) - previous + pot);
+}
+
+// Run the blocks after the current one up to `n`, drawing seeds and slashing on the way.
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		CoinFlipModule::on_initialize(System::block_number());
+	}
+}
+
+// Pick a secret that wins, or loses, the pot of a play whose reveal period opens at block `at`.
+fn secret_for(win: bool, at: u64) -> Secret {
+	let now = System::block_number();
+	System::set_block_number(at);
+	let seed = CoinFlipModule::draw_seed();
+	System::set_block_number(now);
+	(0..=u8::MAX)
+		.map(|i| [i; 32])
+		.find(|secret| CoinFlipModule::is_win(&seed, secret) == win)
+		.expect("about half of the secrets win")
+}
+
+fn commit(table: TableId, who: u64, secret: &Secret) {
+	let commitment = CoinFlipModule::commitment_of(&who, secret);
+	assert_ok!(CoinFlipModule::play(RuntimeOrigin::signed(who), table, commitment));
+}
+
+// Let `who` play at `table` in the current block and win `pot` in the next one.
+fn win(table: TableId, who: u64, pot: u64) {
+	set_pot(table, pot);
+	let next = System::block_number() + 1;
+	let secret = secret_for(true, next);
+	commit(table, who, &secret);
+	run_to_block(next);
+	assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(who), table, secret));
+}
+
+#[test]
+fn genesis_creates_the_pot_account() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(&CoinFlipModule::account_id()), 1);
+	});
+}
+
+#[test]
+fn create_table_should_work() {
+	new_test_ext().execute_with(|| {
+		let house_edge = Permill::from_percent(10);
+		assert_ok!(CoinFlipModule::create_table(RuntimeOrigin::signed(1), 5, house_edge, 100));
+		assert_eq!(
+			CoinFlipModule::tables(0),
+			Some(Table { owner: 1, fee: 5, house_edge, max_pot: 100, pot: 5, pending: 0 })
+		);
+		assert_eq!(CoinFlipModule::next_table_id(), 1);
+		// The pot is seeded by the owner
+		assert_eq!(Balances::total_balance(&1), 5);
+		assert_eq!(Balances::free_balance(&CoinFlipModule::account_id()), 6);
+		System::assert_last_event(
+			Event::TableCreated { table: 0, owner: 1, fee: 5, house_edge, max_pot: 100 }.into(),
+		);
+
+		// Any account can open more tables
+		assert_ok!(CoinFlipModule::create_table(RuntimeOrigin::signed(2), 10, house_edge, 10));
+		assert_eq!(CoinFlipModule::tables(1).map(|info| info.owner), Some(2));
+	});
+}
+
+#[test]
+fn create_table_creates_the_pot_account() {
+	new_test_ext().execute_with(|| {
+		// As if the pallet was added by a runtime upgrade, without genesis
+		let account = CoinFlipModule::account_id();
+		Balances::make_free_balance_be(&account, 0);
+		assert!(!System::account_exists(&account));
+
+		// The owner also pays the existential deposit
+		assert_ok!(CoinFlipModule::create_table(RuntimeOrigin::signed(3), 5, Permill::zero(), 100));
+		assert_eq!(Balances::total_balance(&3), 24);
+		assert_eq!(Balances::free_balance(&account), 6);
+
+		// The whole pot can be paid out
+		win(0, 2, 5);
+		System::assert_last_event(Event::PlayResult { table: 0, player: 2, winnings: 5 }.into());
+		assert_eq!(Balances::free_balance(&account), 6);
+
+		// Once the account exists, tables only cost the fee
+		assert_ok!(CoinFlipModule::create_table(RuntimeOrigin::signed(3), 5, Permill::zero(), 100));
+		assert_eq!(Balances::total_balance(&3), 19);
+	});
+}
+
+#[test]
+fn create_table_security_check_should_work() {
+	new_test_ext().execute_with(|| {
+		let house_edge = Permill::zero();
+		assert_noop!(
+			CoinFlipModule::create_table(RuntimeOrigin::root(), 5, house_edge, 5),
+			BadOrigin
+		);
+		assert_noop!(
+			CoinFlipModule::create_table(RuntimeOrigin::signed(1), 5, house_edge, 4),
+			Error::<Test>::InvalidMaxPot
+		);
+		assert_noop!(
+			CoinFlipModule::create_table(RuntimeOrigin::signed(1), 0, house_edge, 5),
+			Error::<Test>::FeeTooLow
+		);
+		assert_noop!(
+			CoinFlipModule::create_table(RuntimeOrigin::signed(1), 5, house_edge, 0),
+			Error::<Test>::ZeroMaxPot
+		);
+		assert_noop!(
+			CoinFlipModule::create_table(RuntimeOrigin::signed(1), 20, house_edge, 20),
+			BalancesError::<Test, _>::InsufficientBalance
+		);
+	});
+}
 
 #[test]
-fn set_payment_should_work() {
+fn close_table_should_work() {
 	new_test_ext().execute_with(|| {
-		// asserting the function can be called successfully
-		assert_ok!(CoinFlipModule::set_payment(Origin::signed(1), 100));
-		// asserting that the stored value is equal to what we stored
-        assert_eq!(CoinFlipModule::payment(), Some(100));
-		assert_eq!(CoinFlipModule::pot(), 100);
-		
-		// do not update the stored value
-		assert_ok!(CoinFlipModule::set_payment(Origin::signed(1), 200));
-		// asserting that the stored value is equal to what we stored
-        assert_eq!(CoinFlipModule::payment(), Some(100));
-        assert_eq!(CoinFlipModule::pot(), 100);
+		let table = table(10, Permill::zero(), 100, 25);
+
+		assert_noop!(
+			CoinFlipModule::close_table(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::TableNotExist
+		);
+		assert_noop!(
+			CoinFlipModule::close_table(RuntimeOrigin::signed(2), table),
+			Error::<Test>::NotTableOwner
+		);
+
+		// Not while a play is pending
+		commit(table, 2, &[0; 32]);
+		assert_noop!(
+			CoinFlipModule::close_table(RuntimeOrigin::signed(3), table),
+			Error::<Test>::TableBusy
+		);
+		run_to_block(7);
+
+		assert_ok!(CoinFlipModule::close_table(RuntimeOrigin::signed(3), table));
+		assert_eq!(CoinFlipModule::tables(table), None);
+		// 30 - 10 (seed) + 35 (pot)
+		assert_eq!(Balances::total_balance(&3), 55);
+		assert_eq!(Balances::free_balance(&CoinFlipModule::account_id()), 1);
+		System::assert_last_event(Event::TableClosed { table, pot: 35 }.into());
 	});
 }
 
 #[test]
 fn play_security_check_should_work() {
 	new_test_ext().execute_with(|| {
+		let commitment = CoinFlipModule::commitment_of(&2, &[0; 32]);
+
 		// Test ensure_signed
-		assert_noop!(CoinFlipModule::play(Origin::root()), BadOrigin);
+		assert_noop!(CoinFlipModule::play(RuntimeOrigin::root(), 0, commitment), BadOrigin);
 
-		// Ensure the correct error if payment not set
-		assert_noop!(CoinFlipModule::play(Origin::signed(2)), Error::<Test>::NonePaymentValue);
+		// Ensure the correct error if the table is not open
+		assert_noop!(
+			CoinFlipModule::play(RuntimeOrigin::signed(2), 0, commitment),
+			Error::<Test>::TableNotExist
+		);
 
 		// Check the balances in genesis config
-		 assert_eq!(Balances::total_balance(&2), 20);
+		assert_eq!(Balances::total_balance(&2), 20);
 
-		 // set payment and pot, higher than the balances
-		 <Payment<Test>>::put(30);
-		 <Pot<Test>>::put(30);
+		// fee higher than the balances
+		let expensive = table(25, Permill::zero(), 25, 25);
+		assert_noop!(
+			CoinFlipModule::play(RuntimeOrigin::signed(2), expensive, commitment),
+			BalancesError::<Test, _>::InsufficientBalance
+		);
 
-		 assert_noop!(CoinFlipModule::play(Origin::signed(2)), BalancesError::<Test, _>::InsufficientBalance);
+		// fee lower than the balances
+		let cheap = table(2, Permill::zero(), 10, 2);
+		assert_ok!(CoinFlipModule::play(RuntimeOrigin::signed(2), cheap, commitment));
+		assert_eq!(Balances::reserved_balance(&2), 2);
+		assert_eq!(CoinFlipModule::tables(cheap).map(|info| info.pending), Some(1));
+		System::assert_last_event(Event::Committed { table: cheap, player: 2, commitment }.into());
 
-		 // set payment and pot, lower than the balances
-		 <Payment<Test>>::put(10);
-		 <Pot<Test>>::put(10);
-		 assert_ok!(CoinFlipModule::play(Origin::signed(2)));
+		// Only one pending commitment per player and table
+		assert_noop!(
+			CoinFlipModule::play(RuntimeOrigin::signed(2), cheap, commitment),
+			Error::<Test>::AlreadyCommitted
+		);
+	})
+}
+
+#[test]
+fn play_at_several_tables_should_work() {
+	new_test_ext().execute_with(|| {
+		let first = table(2, Permill::zero(), 10, 2);
+		let second = table(3, Permill::zero(), 10, 3);
+
+		commit(first, 2, &[0; 32]);
+		commit(second, 2, &[0; 32]);
+		assert_eq!(Balances::reserved_balance(&2), 5);
+		assert!(CoinFlipModule::commitments(first, 2).is_some());
+		assert!(CoinFlipModule::commitments(second, 2).is_some());
+	})
+}
+
+#[test]
+fn play_fails_with_too_many_commitments_in_a_block() {
+	new_test_ext().execute_with(|| {
+		let table = table(5, Permill::zero(), 100, 5);
+
+		commit(table, 1, &[1; 32]);
+		commit(table, 2, &[2; 32]);
+		assert_noop!(
+			CoinFlipModule::play(
+				RuntimeOrigin::signed(3),
+				table,
+				CoinFlipModule::commitment_of(&3, &[3; 32])
+			),
+			Error::<Test>::TooManyCommitments
+		);
+
+		// The next block has room again
+		run_to_block(2);
+		commit(table, 3, &[3; 32]);
 	})
 }
 
 #[test]
 fn play_should_work_for_win() {
 	new_test_ext().execute_with(|| {
-		<Payment<Test>>::put(10);
-		<Pot<Test>>::put(30);
+		let table = table(10, Permill::zero(), 100, 30);
 		<Nonce<Test>>::put(0);
 
-		assert_ok!(CoinFlipModule::play(Origin::signed(2)));
-		assert_eq!(CoinFlipModule::payment(), Some(10));
-		assert_eq!(CoinFlipModule::pot(), 10);
+		let issuance = Balances::total_issuance();
+		let secret = secret_for(true, 2);
+		commit(table, 2, &secret);
+		assert_eq!(Balances::total_balance(&2), 20);
+
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+		let info = CoinFlipModule::tables(table).unwrap();
+		assert_eq!(info.pot, 10);
+		assert_eq!(info.pending, 0);
 		assert_eq!(Balances::total_balance(&2), 40); // 20 - 10 (payment) + 30 (reward)
+		assert_eq!(Balances::reserved_balance(&2), 0);
 		assert_eq!(CoinFlipModule::nonce(), 1);
+		assert_eq!(CoinFlipModule::commitments(table, 2), None);
+		// The winnings are paid from the pallet account, nothing is minted
+		assert_eq!(Balances::free_balance(&CoinFlipModule::account_id()), 11);
+		assert_eq!(Balances::total_issuance(), issuance);
+		System::assert_last_event(Event::PlayResult { table, player: 2, winnings: 30 }.into());
 	})
 }
 
 #[test]
 fn play_should_work_for_lose() {
 	new_test_ext().execute_with(|| {
-		<Payment<Test>>::put(10);
-		<Pot<Test>>::put(30);
+		let table = table(10, Permill::zero(), 100, 30);
 		<Nonce<Test>>::put(1);
 
-		assert_ok!(CoinFlipModule::play(Origin::signed(2)));
-		assert_eq!(CoinFlipModule::payment(), Some(10));
-		assert_eq!(CoinFlipModule::pot(), 40);
+		let secret = secret_for(false, 2);
+		commit(table, 2, &secret);
+
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+		assert_eq!(CoinFlipModule::tables(table).map(|info| info.pot), Some(40));
 		assert_eq!(Balances::total_balance(&2), 10); // 20 - 10 (payment)
 		assert_eq!(CoinFlipModule::nonce(), 2);
+		System::assert_last_event(Event::PlayResult { table, player: 2, winnings: 0 }.into());
+	})
+}
+
+#[test]
+fn outcome_does_not_depend_on_the_reveal_block() {
+	new_test_ext().execute_with(|| {
+		let table = table(2, Permill::zero(), 100, 5);
+		let secret = secret_for(true, 2);
+		commit(table, 1, &secret);
+		commit(table, 2, &secret);
+
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(1), table, secret));
+		System::assert_last_event(Event::PlayResult { table, player: 1, winnings: 5 }.into());
+
+		// A later block, after another reveal, decides the same secret the same way
+		run_to_block(6);
+		set_pot(table, 4);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+		System::assert_last_event(Event::PlayResult { table, player: 2, winnings: 4 }.into());
+
+		// The seed is dropped once the reveal period is over
+		assert!(CoinFlipModule::reveal_seeds(2).is_some());
+		run_to_block(7);
+		assert_eq!(CoinFlipModule::reveal_seeds(2), None);
+	})
+}
+
+#[test]
+fn house_edge_and_max_pot_go_to_the_owner() {
+	new_test_ext().execute_with(|| {
+		let table = table(10, Permill::from_percent(10), 43, 30);
+		// 30 - 10 (seed)
+		assert_eq!(Balances::total_balance(&3), 20);
+
+		let secret = secret_for(false, 2);
+		commit(table, 2, &secret);
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+
+		// 1 of house edge, 9 to the pot
+		assert_eq!(CoinFlipModule::tables(table).map(|info| info.pot), Some(39));
+		assert_eq!(Balances::total_balance(&3), 21);
+
+		let secret = secret_for(false, 3);
+		commit(table, 2, &secret);
+		run_to_block(3);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+
+		// 1 of house edge, 4 up to the max pot, and the other 5 to the owner
+		assert_eq!(CoinFlipModule::tables(table).map(|info| info.pot), Some(43));
+		assert_eq!(Balances::total_balance(&3), 27);
+		assert_eq!(Balances::total_balance(&2), 0);
+	})
+}
+
+#[test]
+fn treasury_cut_goes_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		TreasuryCut::set(Permill::from_percent(10));
+		let table = table(10, Permill::from_percent(10), 100, 10);
+		let issuance = Balances::total_issuance();
+
+		let secret = secret_for(false, 2);
+		commit(table, 2, &secret);
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+
+		// 1 to the treasury, 1 of house edge, 8 to the pot
+		assert_eq!(Balances::total_balance(&TREASURY), 1);
+		assert_eq!(Balances::total_balance(&3), 21);
+		assert_eq!(CoinFlipModule::tables(table).map(|info| info.pot), Some(18));
+		assert_eq!(Balances::free_balance(&CoinFlipModule::account_id()), 19);
+		assert_eq!(Balances::total_issuance(), issuance);
+	})
+}
+
+#[test]
+fn reveal_security_check_should_work() {
+	new_test_ext().execute_with(|| {
+		let table = table(10, Permill::zero(), 100, 10);
+
+		assert_noop!(CoinFlipModule::reveal(RuntimeOrigin::root(), table, [0; 32]), BadOrigin);
+		assert_noop!(
+			CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, [0; 32]),
+			Error::<Test>::NoCommitment
+		);
+
+		commit(table, 2, &[0; 32]);
+
+		// Not in the block of the commitment
+		assert_noop!(
+			CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, [0; 32]),
+			Error::<Test>::RevealTooEarly
+		);
+
+		run_to_block(2);
+		assert_noop!(
+			CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, [1; 32]),
+			Error::<Test>::InvalidSecret
+		);
+
+		// Nor once the reveal period is over
+		System::set_block_number(7);
+		assert_noop!(
+			CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, [0; 32]),
+			Error::<Test>::RevealTooLate
+		);
+	})
+}
+
+#[test]
+fn reveal_is_mixed_into_later_plays() {
+	new_test_ext().execute_with(|| {
+		let table = table(5, Permill::zero(), 100, 5);
+
+		commit(table, 2, &[0; 32]);
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, [0; 32]));
+
+		let entropy = CoinFlipModule::entropy();
+		assert_ne!(entropy, Default::default());
+
+		commit(table, 1, &[0; 32]);
+		run_to_block(3);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(1), table, [0; 32]));
+		assert_ne!(CoinFlipModule::entropy(), entropy);
+	})
+}
+
+#[test]
+fn unrevealed_commitment_is_slashed() {
+	new_test_ext().execute_with(|| {
+		let table = table(10, Permill::zero(), 100, 30);
+
+		commit(table, 2, &[0; 32]);
+
+		// Still in the reveal period
+		CoinFlipModule::on_initialize(6);
+		assert!(CoinFlipModule::commitments(table, 2).is_some());
+
+		run_to_block(7);
+		assert_eq!(CoinFlipModule::commitments(table, 2), None);
+		assert_eq!(Balances::total_balance(&2), 10);
+		let info = CoinFlipModule::tables(table).unwrap();
+		assert_eq!(info.pot, 40);
+		assert_eq!(info.pending, 0);
+		System::assert_last_event(Event::CommitmentSlashed { table, player: 2, stake: 10 }.into());
+	})
+}
+
+#[test]
+fn revealed_commitment_is_not_slashed() {
+	new_test_ext().execute_with(|| {
+		let table = table(10, Permill::zero(), 100, 30);
+
+		let secret = secret_for(false, 2);
+		commit(table, 2, &secret);
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+
+		// Committing again in a later block is not slashed at the first deadline
+		Balances::make_free_balance_be(&2, 20);
+		commit(table, 2, &[0; 32]);
+
+		run_to_block(7);
+		assert!(CoinFlipModule::commitments(table, 2).is_some());
+		assert_eq!(CoinFlipModule::tables(table).map(|info| info.pot), Some(40));
+		assert_eq!(Balances::total_balance(&2), 20);
+	})
+}
+
+#[test]
+fn stats_are_recorded() {
+	new_test_ext().execute_with(|| {
+		let table = table(2, Permill::zero(), 100, 2);
+		assert_eq!(CoinFlipModule::stats(2), Stats::default());
+
+		win(table, 2, 5);
+
+		let secret = secret_for(false, 3);
+		commit(table, 2, &secret);
+		run_to_block(3);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), table, secret));
+
+		// Unrevealed plays count as lost
+		commit(table, 2, &[0; 32]);
+		run_to_block(9);
+
+		assert_eq!(
+			CoinFlipModule::stats(2),
+			Stats { plays: 3, wins: 1, total_wagered: 6, total_won: 5 }
+		);
+		assert_eq!(CoinFlipModule::stats(1), Stats::default());
+	})
+}
+
+#[test]
+fn leaderboard_keeps_the_best_winners() {
+	new_test_ext().execute_with(|| {
+		let table = table(2, Permill::zero(), 100, 2);
+
+		win(table, 1, 5);
+		assert_eq!(CoinFlipModule::leaderboard().into_inner(), vec![(1, 5)]);
+
+		win(table, 2, 3);
+		assert_eq!(CoinFlipModule::leaderboard().into_inner(), vec![(1, 5), (2, 3)]);
+
+		// Only the best two are kept
+		win(table, 3, 4);
+		assert_eq!(CoinFlipModule::leaderboard().into_inner(), vec![(1, 5), (3, 4)]);
+
+		// Losing does not change the board
+		let secret = secret_for(false, 5);
+		commit(table, 1, &secret);
+		run_to_block(5);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(1), table, secret));
+		assert_eq!(CoinFlipModule::leaderboard().into_inner(), vec![(1, 5), (3, 4)]);
+
+		// Winnings add up
+		win(table, 2, 10);
+		assert_eq!(CoinFlipModule::leaderboard().into_inner(), vec![(2, 13), (1, 5)]);
+	})
+}
+
+#[test]
+fn play_is_rate_limited() {
+	new_test_ext().execute_with(|| {
+		let tables: Vec<_> = (0..5).map(|_| table(1, Permill::zero(), 100, 1)).collect();
+
+		for (block, table) in tables.iter().take(3).enumerate() {
+			System::set_block_number(block as u64 + 1);
+			commit(*table, 2, &[0; 32]);
+		}
+
+		run_to_block(4);
+		assert_noop!(
+			CoinFlipModule::play(
+				RuntimeOrigin::signed(2),
+				tables[3],
+				CoinFlipModule::commitment_of(&2, &[0; 32])
+			),
+			Error::<Test>::TooManyPlays
+		);
+
+		// A new period starts `PlayPeriod` blocks after the first play
+		run_to_block(11);
+		commit(tables[3], 2, &[0; 32]);
+	})
+}
+
+#[test]
+fn loss_limit_is_enforced() {
+	new_test_ext().execute_with(|| {
+		let tables: Vec<_> = (0..4).map(|_| table(2, Permill::zero(), 100, 2)).collect();
+
+		assert_ok!(CoinFlipModule::set_loss_limit(RuntimeOrigin::signed(2), Some(5)));
+		System::assert_last_event(Event::LossLimitSet { who: 2, limit: Some(5) }.into());
+
+		// Pending plays count as lost
+		commit(tables[0], 2, &[0; 32]);
+		run_to_block(2);
+		commit(tables[1], 2, &[0; 32]);
+		assert_eq!(CoinFlipModule::losses(2).lost, 4);
+
+		run_to_block(3);
+		assert_noop!(
+			CoinFlipModule::play(
+				RuntimeOrigin::signed(2),
+				tables[2],
+				CoinFlipModule::commitment_of(&2, &[0; 32])
+			),
+			Error::<Test>::LossLimitExceeded
+		);
+
+		// A new period starts `LossLimitPeriod` blocks after the first play
+		run_to_block(21);
+		commit(tables[2], 2, &[0; 32]);
+		assert_eq!(CoinFlipModule::losses(2), LossWindow { start: 21, lost: 2 });
+	})
+}
+
+#[test]
+fn winnings_make_up_for_losses() {
+	new_test_ext().execute_with(|| {
+		let tables: Vec<_> = (0..3).map(|_| table(2, Permill::zero(), 100, 2)).collect();
+		assert_ok!(CoinFlipModule::set_loss_limit(RuntimeOrigin::signed(2), Some(4)));
+
+		win(tables[0], 2, 5);
+		assert_eq!(CoinFlipModule::losses(2).lost, 0);
+
+		commit(tables[1], 2, &[0; 32]);
+		run_to_block(3);
+		commit(tables[2], 2, &[0; 32]);
+		assert_eq!(CoinFlipModule::losses(2).lost, 4);
+	})
+}
+
+#[test]
+fn winnings_only_make_up_for_losses_of_their_period() {
+	new_test_ext().execute_with(|| {
+		let tables: Vec<_> = (0..3).map(|_| table(2, Permill::zero(), 100, 2)).collect();
+
+		// The period starts with the first play
+		commit(tables[0], 2, &[0; 32]);
+		run_to_block(20);
+
+		// A play committed at the end of the period is revealed in the next one
+		let secret = secret_for(true, 21);
+		commit(tables[1], 2, &secret);
+		assert_eq!(CoinFlipModule::losses(2), LossWindow { start: 1, lost: 4 });
+		run_to_block(21);
+		commit(tables[2], 2, &[0; 32]);
+		assert_eq!(CoinFlipModule::losses(2), LossWindow { start: 21, lost: 2 });
+
+		set_pot(tables[1], 5);
+		assert_ok!(CoinFlipModule::reveal(RuntimeOrigin::signed(2), tables[1], secret));
+		System::assert_last_event(
+			Event::PlayResult { table: tables[1], player: 2, winnings: 5 }.into(),
+		);
+		assert_eq!(CoinFlipModule::losses(2), LossWindow { start: 21, lost: 2 });
+	})
+}
+
+#[test]
+fn raising_loss_limit_is_delayed() {
+	new_test_ext().execute_with(|| {
+		// Setting a first limit lowers it from no limit
+		assert_ok!(CoinFlipModule::set_loss_limit(RuntimeOrigin::signed(2), Some(5)));
+		assert_eq!(CoinFlipModule::loss_limit(&2, 1), Some(5));
+
+		assert_ok!(CoinFlipModule::set_loss_limit(RuntimeOrigin::signed(2), Some(10)));
+		System::assert_last_event(
+			Event::LossLimitScheduled { who: 2, limit: Some(10), at: 6 }.into(),
+		);
+		assert_eq!(CoinFlipModule::loss_limit(&2, 5), Some(5));
+		assert_eq!(CoinFlipModule::loss_limit(&2, 6), Some(10));
+
+		// Lowering takes effect immediately, and drops the pending raise
+		run_to_block(2);
+		assert_ok!(CoinFlipModule::set_loss_limit(RuntimeOrigin::signed(2), Some(3)));
+		assert_eq!(CoinFlipModule::loss_limit(&2, 2), Some(3));
+		assert_eq!(CoinFlipModule::loss_limit(&2, 6), Some(3));
+
+		// Removing the limit is a raise too
+		assert_ok!(CoinFlipModule::set_loss_limit(RuntimeOrigin::signed(2), None));
+		System::assert_last_event(Event::LossLimitScheduled { who: 2, limit: None, at: 7 }.into());
+		assert_eq!(CoinFlipModule::loss_limit(&2, 6), Some(3));
+		assert_eq!(CoinFlipModule::loss_limit(&2, 7), None);
+
+		assert_noop!(CoinFlipModule::set_loss_limit(RuntimeOrigin::root(), None), BadOrigin);
 	})
 }
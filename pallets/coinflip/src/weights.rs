@@ -0,0 +1,220 @@
+//! Weights for pallet_coinflip
+//!
+//! These are estimates counted from the storage accesses of each call, not benchmark results.
+//! Regenerate this file with `benchmark pallet --pallet pallet_coinflip` before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_coinflip.
+pub trait WeightInfo {
+	fn create_table() -> Weight;
+	fn close_table() -> Weight;
+	fn play() -> Weight;
+	fn reveal() -> Weight;
+	fn on_initialize(n: u32, t: u32, ) -> Weight;
+	fn set_loss_limit() -> Weight;
+}
+
+/// Weights for pallet_coinflip using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `CoinFlipModule::NextTableId` (r:1 w:1)
+	/// Proof: `CoinFlipModule::NextTableId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Tables` (r:0 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	fn create_table() -> Weight {
+		Weight::from_parts(37_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `CoinFlipModule::Tables` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn close_table() -> Weight {
+		Weight::from_parts(36_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `CoinFlipModule::Tables` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Commitments` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Commitments` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::PlayWindows` (r:1 w:1)
+	/// Proof: `CoinFlipModule::PlayWindows` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::LossLimits` (r:1 w:0)
+	/// Proof: `CoinFlipModule::LossLimits` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Losses` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Losses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::RevealDeadlines` (r:1 w:1)
+	/// Proof: `CoinFlipModule::RevealDeadlines` (`max_values`: None, `max_size`: Some(2318), added: 4793, mode: `MaxEncodedLen`)
+	fn play() -> Weight {
+		Weight::from_parts(51_000_000, 5783)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `CoinFlipModule::Commitments` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Commitments` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::RevealSeeds` (r:1 w:0)
+	/// Proof: `CoinFlipModule::RevealSeeds` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Tables` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Losses` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Losses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:3)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::PlayerStats` (r:1 w:1)
+	/// Proof: `CoinFlipModule::PlayerStats` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Leaderboard` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Leaderboard` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Nonce` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Nonce` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Entropy` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Entropy` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn reveal() -> Weight {
+		Weight::from_parts(78_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: `CoinFlipModule::RevealDeadlines` (r:2 w:1)
+	/// Proof: `CoinFlipModule::RevealDeadlines` (`max_values`: None, `max_size`: Some(2318), added: 4793, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::RevealSeeds` (r:0 w:2)
+	/// Proof: `CoinFlipModule::RevealSeeds` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `RandomnessCollectiveFlip::RandomMaterial` (r:1 w:0)
+	/// Proof: `RandomnessCollectiveFlip::RandomMaterial` (`max_values`: Some(1), `max_size`: Some(2594), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Entropy` (r:1 w:0)
+	/// Proof: `CoinFlipModule::Entropy` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Commitments` (r:64 w:64)
+	/// Proof: `CoinFlipModule::Commitments` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Tables` (r:64 w:64)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:130 w:130)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::PlayerStats` (r:64 w:64)
+	/// Proof: `CoinFlipModule::PlayerStats` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 64]`.
+	/// The range of component `t` is `[1, 64]`.
+	fn on_initialize(n: u32, t: u32, ) -> Weight {
+		Weight::from_parts(9_000_000, 5783)
+			.saturating_add(Weight::from_parts(38_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(n.into()))
+	}
+	/// Storage: `CoinFlipModule::LossLimits` (r:1 w:1)
+	/// Proof: `CoinFlipModule::LossLimits` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	fn set_loss_limit() -> Weight {
+		Weight::from_parts(13_000_000, 3554)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: `CoinFlipModule::NextTableId` (r:1 w:1)
+	/// Proof: `CoinFlipModule::NextTableId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Tables` (r:0 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	fn create_table() -> Weight {
+		Weight::from_parts(37_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `CoinFlipModule::Tables` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn close_table() -> Weight {
+		Weight::from_parts(36_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `CoinFlipModule::Tables` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Commitments` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Commitments` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::PlayWindows` (r:1 w:1)
+	/// Proof: `CoinFlipModule::PlayWindows` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::LossLimits` (r:1 w:0)
+	/// Proof: `CoinFlipModule::LossLimits` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Losses` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Losses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::RevealDeadlines` (r:1 w:1)
+	/// Proof: `CoinFlipModule::RevealDeadlines` (`max_values`: None, `max_size`: Some(2318), added: 4793, mode: `MaxEncodedLen`)
+	fn play() -> Weight {
+		Weight::from_parts(51_000_000, 5783)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `CoinFlipModule::Commitments` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Commitments` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::RevealSeeds` (r:1 w:0)
+	/// Proof: `CoinFlipModule::RevealSeeds` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Tables` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Losses` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Losses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:3)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::PlayerStats` (r:1 w:1)
+	/// Proof: `CoinFlipModule::PlayerStats` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Leaderboard` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Leaderboard` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Nonce` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Nonce` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Entropy` (r:1 w:1)
+	/// Proof: `CoinFlipModule::Entropy` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn reveal() -> Weight {
+		Weight::from_parts(78_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: `CoinFlipModule::RevealDeadlines` (r:2 w:1)
+	/// Proof: `CoinFlipModule::RevealDeadlines` (`max_values`: None, `max_size`: Some(2318), added: 4793, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::RevealSeeds` (r:0 w:2)
+	/// Proof: `CoinFlipModule::RevealSeeds` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `RandomnessCollectiveFlip::RandomMaterial` (r:1 w:0)
+	/// Proof: `RandomnessCollectiveFlip::RandomMaterial` (`max_values`: Some(1), `max_size`: Some(2594), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Entropy` (r:1 w:0)
+	/// Proof: `CoinFlipModule::Entropy` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Commitments` (r:64 w:64)
+	/// Proof: `CoinFlipModule::Commitments` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::Tables` (r:64 w:64)
+	/// Proof: `CoinFlipModule::Tables` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:130 w:130)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `CoinFlipModule::PlayerStats` (r:64 w:64)
+	/// Proof: `CoinFlipModule::PlayerStats` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 64]`.
+	/// The range of component `t` is `[1, 64]`.
+	fn on_initialize(n: u32, t: u32, ) -> Weight {
+		Weight::from_parts(9_000_000, 5783)
+			.saturating_add(Weight::from_parts(38_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(n.into()))
+	}
+	/// Storage: `CoinFlipModule::LossLimits` (r:1 w:1)
+	/// Proof: `CoinFlipModule::LossLimits` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	fn set_loss_limit() -> Weight {
+		Weight::from_parts(13_000_000, 3554)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -0,0 +1,32 @@
+//! Runtime API definitions of the offchain worker send signed and unsigned transaction pallets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use ocw_common::aggregation::PriceAggregates;
+
+sp_api::decl_runtime_apis! {
+	/// Queries of the prices kept by the offchain worker send signed transaction pallet.
+	pub trait OcwSignedApi<BlockNumber>
+	where
+		BlockNumber: Codec,
+	{
+		/// The aggregates of the prices of `asset` in `quote` over the last `window` blocks.
+		///
+		/// Only the latest `MaxPrices` prices of a pair are kept on chain, whatever the window.
+		fn aggregates(asset: Vec<u8>, quote: Vec<u8>, window: BlockNumber) -> PriceAggregates;
+	}
+
+	/// Queries of the prices kept by the offchain worker send unsigned transaction pallet.
+	pub trait OcwUnsignedApi<BlockNumber>
+	where
+		BlockNumber: Codec,
+	{
+		/// The aggregates of the prices of `asset` in `quote` over the last `window` blocks.
+		///
+		/// Only the latest `MaxPrices` prices of a pair are kept on chain, whatever the window.
+		fn aggregates(asset: Vec<u8>, quote: Vec<u8>, window: BlockNumber) -> PriceAggregates;
+	}
+}
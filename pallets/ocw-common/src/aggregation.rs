@@ -0,0 +1,99 @@
+//! Aggregates of the price samples of a pair.
+//!
+//! A sample is the block a price was submitted in and the price, samples are ordered oldest
+//! first.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, CheckedDiv, Saturating, UniqueSaturatedInto, Zero},
+	FixedPointNumber, FixedU128,
+};
+use sp_std::vec::Vec;
+
+/// Aggregates of the prices of a pair over a block window.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, Debug, TypeInfo)]
+pub struct PriceAggregates {
+	/// The mean of the prices submitted in the window.
+	pub mean: Option<FixedU128>,
+	/// The median of the prices submitted in the window.
+	pub median: Option<FixedU128>,
+	/// The time-weighted average price over the window.
+	pub twap: Option<FixedU128>,
+}
+
+/// The aggregates of `samples` over the last `window` blocks up to block `now`.
+///
+/// The mean and the median are those of the prices submitted after block `now - window`.
+pub fn aggregates<BlockNumber>(
+	samples: &[(BlockNumber, FixedU128)],
+	now: BlockNumber,
+	window: BlockNumber,
+) -> PriceAggregates
+where
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	let start = now.saturating_sub(window);
+	let prices: Vec<_> =
+		samples.iter().filter(|(at, _)| *at > start).map(|(_, price)| *price).collect();
+	PriceAggregates {
+		mean: mean(&prices),
+		median: median(&prices),
+		twap: time_weighted_average(samples, start, now),
+	}
+}
+
+/// The mean of `prices`, `None` if there are none.
+pub fn mean(prices: &[FixedU128]) -> Option<FixedU128> {
+	if prices.is_empty() {
+		return None
+	}
+	let sum = prices.iter().fold(FixedU128::zero(), |sum, price| sum.saturating_add(*price));
+	sum.checked_div(&FixedU128::saturating_from_integer(prices.len() as u128))
+}
+
+/// The median of `prices`, the mean of the two middle prices for an even number of them, `None`
+/// if there are none.
+pub fn median(prices: &[FixedU128]) -> Option<FixedU128> {
+	let mut prices: Vec<_> = prices.to_vec();
+	prices.sort();
+	let middle = prices.len() / 2;
+	match prices.len() {
+		0 => None,
+		len if len % 2 == 1 => Some(prices[middle]),
+		_ => mean(&prices[middle - 1..=middle]),
+	}
+}
+
+/// The time-weighted average of the prices from block `start` to block `end`.
+///
+/// Each price holds from its block until the block of the next sample, so the last sample before
+/// `start` counts for the beginning of the window. If no price held for any block of the window,
+/// e.g. all of them were submitted at `end`, the latest price submitted in the window is returned.
+pub fn time_weighted_average<BlockNumber>(
+	samples: &[(BlockNumber, FixedU128)],
+	start: BlockNumber,
+	end: BlockNumber,
+) -> Option<FixedU128>
+where
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	let mut weighted = FixedU128::zero();
+	let mut blocks = 0u128;
+	for (index, (at, price)) in samples.iter().enumerate() {
+		let from = (*at).max(start);
+		let until = samples.get(index + 1).map_or(end, |(next, _)| *next).min(end);
+		if until <= from {
+			continue
+		}
+		let span: u128 = (until - from).unique_saturated_into();
+		weighted =
+			weighted.saturating_add(price.saturating_mul(FixedU128::saturating_from_integer(span)));
+		blocks = blocks.saturating_add(span);
+	}
+
+	if blocks.is_zero() {
+		return samples.last().filter(|(at, _)| *at >= start).map(|(_, price)| *price)
+	}
+	weighted.checked_div(&FixedU128::saturating_from_integer(blocks))
+}
@@ -0,0 +1,429 @@
+//! The offchain side shared by the offchain worker pallets.
+//!
+//! Each pallet describes its offchain worker with a [`Worker`], which keeps the configuration
+//! and the state of its runs apart in the offchain local storage, and uses it to fetch the price
+//! of a pair over HTTP, back off after failed fetches and run at most once per block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod aggregation;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+
+#[cfg(test)]
+mod tests;
+
+extern crate alloc;
+
+use alloc::{format, string::String};
+use codec::{Codec, Decode, Encode};
+use frame_support::{
+	traits::{ConstU32, Get},
+	BoundedVec,
+};
+use frame_system::offchain::{AppCrypto, SigningTypes};
+use sp_core::offchain::StorageKind;
+use sp_runtime::{
+	offchain::{
+		http,
+		storage::StorageValueRef,
+		storage_lock::{BlockAndTime, StorageLock},
+		Duration,
+	},
+	traits::{AtLeast32BitUnsigned, BlockNumberProvider, IdentifyAccount, Saturating},
+	RuntimeAppPublic,
+};
+use sp_std::{fmt::Debug, vec::Vec};
+
+pub use sp_runtime::{FixedPointNumber, FixedU128};
+
+/// Length limit of an asset or quote symbol.
+pub const MAX_SYMBOL_LEN: u32 = 16;
+
+/// The ticker symbol of an asset, e.g. `BTC`.
+pub type AssetId = BoundedVec<u8, ConstU32<MAX_SYMBOL_LEN>>;
+
+/// The ticker symbol of the currency an asset is priced in, e.g. `USD`.
+pub type QuoteId = BoundedVec<u8, ConstU32<MAX_SYMBOL_LEN>>;
+
+/// A lock left behind by a run, e.g. one that panicked, expires once both this many blocks and
+/// `LOCK_TIMEOUT_EXPIRATION` have passed.
+pub const LOCK_BLOCK_EXPIRATION: u32 = 3;
+/// How long a run may hold the lock, in milliseconds, well beyond the HTTP timeout.
+pub const LOCK_TIMEOUT_EXPIRATION: u64 = 20_000;
+/// The longest the offchain worker waits, in blocks, before fetching again after failures.
+pub const MAX_FETCH_BACKOFF: u32 = 32;
+
+/// The URL the price is fetched from, unless one is stored under the `url_key` of the worker.
+pub const DEFAULT_URL: &str =
+	"https://min-api.cryptocompare.com/data/price?fsym={asset}&tsyms={quote}";
+/// The path of the price in the JSON response of `DEFAULT_URL`, unless one is stored under the
+/// `json_path_key` of the worker.
+pub const DEFAULT_JSON_PATH: &str = "{quote}";
+
+/// The offchain worker of a pallet: where it keeps its configuration and state in the offchain
+/// local storage, and how it logs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Worker {
+	/// The target of the log messages.
+	pub log_target: &'static str,
+	/// Offchain local storage key of the URL the price is fetched from.
+	///
+	/// `{asset}` and `{quote}` in the URL, as well as in the JSON path, are replaced by the
+	/// symbols of the pair being fetched.
+	pub url_key: &'static [u8],
+	/// Offchain local storage key of the dot separated path of the price in the JSON response.
+	pub json_path_key: &'static [u8],
+	/// Offchain local storage key of the HTTP timeout, in milliseconds.
+	pub timeout_key: &'static [u8],
+	/// The HTTP timeout, in milliseconds, unless one is stored under `timeout_key`.
+	pub default_timeout: u64,
+	/// Offchain local storage key of the last block a price was submitted for, SCALE encoded.
+	pub last_send_key: &'static [u8],
+	/// Offchain local storage key of the lock held by a running offchain worker.
+	pub lock_key: &'static [u8],
+	/// Prefix of the offchain local storage keys of the `FetchFailures` of each pair, SCALE
+	/// encoded, see [`Worker::failures_key_of`].
+	pub failures_key: &'static [u8],
+}
+
+/// Where the offchain worker fetches the price from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PriceSource {
+	/// The URL of the HTTP GET request.
+	pub url: String,
+	/// The dot separated path of the price in the JSON response, e.g. `data.0.price`.
+	pub json_path: String,
+	/// How long to wait for the response, in milliseconds.
+	pub timeout: u64,
+}
+
+impl PriceSource {
+	/// The URL and JSON path of the price of `asset` in `quote`.
+	pub fn for_pair(&self, asset: &str, quote: &str) -> (String, String) {
+		let fill = |template: &str| template.replace("{asset}", asset).replace("{quote}", quote);
+		(fill(&self.url), fill(&self.json_path))
+	}
+}
+
+/// Why fetching a price failed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FetchError {
+	/// The request could not be sent, or timed out.
+	Http(http::Error),
+	/// The response status is not `200`.
+	Status(u16),
+	/// The response body, or a symbol, is not UTF-8.
+	Utf8,
+	/// The response body is not JSON.
+	Json,
+	/// There is no price at the JSON path, that is a non-negative number fitting a `FixedU128`.
+	MissingField,
+}
+
+/// Why a run of the offchain worker submitted no price.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SendError {
+	/// There is nothing to submit in this block, e.g. while backing off after failed fetches.
+	Skipped(&'static str),
+	/// Fetching or submitting the price failed.
+	Failed(&'static str),
+}
+
+/// The failed fetches of the price of a pair, kept in the offchain local storage under the
+/// [`Worker::failures_key_of`] the pair.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, Debug)]
+pub struct FetchFailures<BlockNumber> {
+	/// Failures since the last successful fetch.
+	pub consecutive: u32,
+	/// The first block to fetch at again.
+	pub retry_at: BlockNumber,
+	/// All the requests which could not be sent, or timed out.
+	pub http: u32,
+	/// All the responses with a status other than `200`.
+	pub status: u32,
+	/// All the response bodies, or symbols, which are not UTF-8.
+	pub utf8: u32,
+	/// All the response bodies which are not JSON.
+	pub json: u32,
+	/// All the responses without a price at the JSON path.
+	pub missing_field: u32,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> FetchFailures<BlockNumber> {
+	/// Count `error`, and wait twice as many blocks as after the previous failure before fetching
+	/// again, from the next block after a first failure up to `MAX_FETCH_BACKOFF` blocks.
+	pub fn record(&mut self, error: FetchError, block_number: BlockNumber) {
+		let count = match error {
+			FetchError::Http(_) => &mut self.http,
+			FetchError::Status(_) => &mut self.status,
+			FetchError::Utf8 => &mut self.utf8,
+			FetchError::Json => &mut self.json,
+			FetchError::MissingField => &mut self.missing_field,
+		};
+		*count = count.saturating_add(1);
+
+		let backoff = 1u32.checked_shl(self.consecutive).unwrap_or(u32::MAX).min(MAX_FETCH_BACKOFF);
+		self.consecutive = self.consecutive.saturating_add(1);
+		self.retry_at = block_number.saturating_add(backoff.into());
+	}
+}
+
+impl Worker {
+	/// Read the price source from the offchain local storage, falling back to the defaults for
+	/// any value missing or not valid UTF-8.
+	///
+	/// Values are stored as raw UTF-8 strings, as written by `offchain_localStorageSet`.
+	pub fn price_source(&self) -> PriceSource {
+		let read = |key: &[u8]| {
+			sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, key)
+				.and_then(|value| String::from_utf8(value).ok())
+		};
+
+		PriceSource {
+			url: read(self.url_key).unwrap_or_else(|| DEFAULT_URL.into()),
+			json_path: read(self.json_path_key).unwrap_or_else(|| DEFAULT_JSON_PATH.into()),
+			timeout: read(self.timeout_key)
+				.and_then(|timeout| timeout.trim().parse().ok())
+				.unwrap_or(self.default_timeout),
+		}
+	}
+
+	/// The offchain local storage key of the `FetchFailures` of the pair of `asset` in `quote`.
+	pub fn failures_key_of(&self, asset: &[u8], quote: &[u8]) -> Vec<u8> {
+		let mut key = self.failures_key.to_vec();
+		(asset, quote).encode_to(&mut key);
+		key
+	}
+
+	/// Run `send` to submit the price of `block_number`, and remember the block once it
+	/// succeeds.
+	///
+	/// Runs overlapping another one, e.g. while syncing, skip the block rather than submit the
+	/// price twice, and so do blocks imported again, e.g. on another fork.
+	pub fn run_once<B>(
+		&self,
+		block_number: B::BlockNumber,
+		send: impl FnOnce() -> Result<(), SendError>,
+	) where
+		B: BlockNumberProvider,
+		B::BlockNumber: Debug,
+	{
+		let mut lock = StorageLock::<BlockAndTime<B>>::with_block_and_time_deadline(
+			self.lock_key,
+			LOCK_BLOCK_EXPIRATION,
+			Duration::from_millis(LOCK_TIMEOUT_EXPIRATION),
+		);
+		let Ok(_guard) = lock.try_lock() else {
+			log::info!(target: self.log_target, "Another offchain worker run holds the lock");
+			return
+		};
+
+		let last_send = StorageValueRef::persistent(self.last_send_key);
+		if let Ok(Some(last)) = last_send.get::<B::BlockNumber>() {
+			if last >= block_number {
+				log::info!(target: self.log_target, "Price already submitted at block {:?}", last);
+				return
+			}
+		}
+
+		match send() {
+			Ok(()) => last_send.set(&block_number),
+			Err(SendError::Skipped(reason)) =>
+				log::debug!(target: self.log_target, "Offchain worker skipped: {}", reason),
+			Err(SendError::Failed(e)) =>
+				log::error!(target: self.log_target, "Offchain worker failed: {}", e),
+		}
+	}
+
+	/// Fetch the price of a pair, unless the previous fetches of the pair failed and the
+	/// offchain worker backs off until a later block, keeping count of the failures.
+	pub fn fetch_price_with_backoff<BlockNumber>(
+		&self,
+		block_number: BlockNumber,
+		asset: &[u8],
+		quote: &[u8],
+	) -> Result<FixedU128, SendError>
+	where
+		BlockNumber: AtLeast32BitUnsigned + Codec + Copy + Debug,
+	{
+		let key = self.failures_key_of(asset, quote);
+		let storage = StorageValueRef::persistent(&key);
+		let mut failures =
+			storage.get::<FetchFailures<BlockNumber>>().ok().flatten().unwrap_or_default();
+		if block_number < failures.retry_at {
+			log::debug!(target: self.log_target, "Backing off until block {:?}", failures.retry_at);
+			return Err(SendError::Skipped("Backing off after failed fetches"))
+		}
+
+		match self.fetch_price(asset, quote) {
+			Ok(price) => {
+				if failures.consecutive > 0 {
+					failures.consecutive = 0;
+					storage.set(&failures);
+				}
+				Ok(price)
+			},
+			Err(e) => {
+				log::warn!(target: self.log_target, "Failed to fetch price: {:?}", e);
+				failures.record(e, block_number);
+				storage.set(&failures);
+				Err(SendError::Failed("Failed to fetch price"))
+			},
+		}
+	}
+
+	/// Fetch the price of `asset` in `quote` from the price source.
+	pub fn fetch_price(&self, asset: &[u8], quote: &[u8]) -> Result<FixedU128, FetchError> {
+		let source = self.price_source();
+		// Symbols of registered pairs are ASCII.
+		let (url, json_path) = source.for_pair(
+			sp_std::str::from_utf8(asset).map_err(|_| FetchError::Utf8)?,
+			sp_std::str::from_utf8(quote).map_err(|_| FetchError::Utf8)?,
+		);
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(source.timeout));
+		// Initiate an external HTTP GET request.
+		// This is using high-level wrappers from `sp_runtime`, for the low-level calls that
+		// you can find in `sp_io`. The API is trying to be similar to `reqwest`, but
+		// since we are running in a custom WASM execution environment we can't simply
+		// import the library here.
+		let request = http::Request::get(&url);
+		// We set the deadline for sending of the request, note that awaiting response can
+		// have a separate deadline. Next we send the request, before that it's also possible
+		// to alter request headers or stream body content in case of non-GET requests.
+		let pending = request
+			.deadline(deadline)
+			.send()
+			.map_err(|_| FetchError::Http(http::Error::IoError))?;
+		// The request is already being processed by the host, we are free to do anything
+		// else in the worker (we can send multiple concurrent requests too).
+		// At some point however we probably want to check the response though,
+		// so we can block current thread and wait for it to finish.
+		// Note that since the request is being driven by the host, we don't have to wait
+		// for the request to have it complete, we will just not read the response.
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| FetchError::Http(http::Error::DeadlineReached))?
+			.map_err(FetchError::Http)?;
+
+		if response.code != 200 {
+			return Err(FetchError::Status(response.code))
+		}
+
+		let body = response.body().collect::<Vec<u8>>();
+
+		let body_str = sp_std::str::from_utf8(&body).map_err(|_| FetchError::Utf8)?;
+
+		let price = parse_price(body_str, &json_path).map_err(|e| {
+			log::debug!(target: self.log_target, "No price at `{}` in {}", json_path, body_str);
+			e
+		})?;
+		log::info!(target: self.log_target, "Got price: {:?}", price);
+
+		Ok(price)
+	}
+}
+
+/// The keys in the keystore, of the `C` crypto, of the accounts among `accounts`.
+///
+/// The offchain workers only sign with these, anything signed by the other keys would be
+/// rejected.
+pub fn local_keys<T, C>(accounts: &[T::AccountId]) -> Vec<T::Public>
+where
+	T: SigningTypes,
+	C: AppCrypto<T::Public, T::Signature>,
+{
+	C::RuntimeAppPublic::all()
+		.into_iter()
+		.map(|key| -> T::Public { C::GenericPublic::from(key).into() })
+		.filter(|public| accounts.contains(&public.clone().into_account()))
+		.collect()
+}
+
+/// Why registering or deregistering a pair failed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PairError {
+	/// The symbol is empty or not ASCII alphanumeric.
+	InvalidSymbol,
+	/// The pair is already registered.
+	AlreadyRegistered,
+	/// The pair is not registered.
+	NotRegistered,
+	/// The registered pairs are at their limit.
+	TooMany,
+}
+
+/// Add the pair of `asset` in `quote` to the registered `pairs`.
+pub fn register_pair<S: Get<u32>>(
+	pairs: &mut BoundedVec<(AssetId, QuoteId), S>,
+	asset: AssetId,
+	quote: QuoteId,
+) -> Result<(), PairError> {
+	if !is_valid_symbol(&asset) || !is_valid_symbol(&quote) {
+		return Err(PairError::InvalidSymbol)
+	}
+	let pair = (asset, quote);
+	if pairs.contains(&pair) {
+		return Err(PairError::AlreadyRegistered)
+	}
+	pairs.try_push(pair).map_err(|_| PairError::TooMany)
+}
+
+/// Remove `pair` from the registered `pairs`.
+pub fn deregister_pair<S: Get<u32>>(
+	pairs: &mut BoundedVec<(AssetId, QuoteId), S>,
+	pair: &(AssetId, QuoteId),
+) -> Result<(), PairError> {
+	let index = pairs.iter().position(|p| p == pair).ok_or(PairError::NotRegistered)?;
+	pairs.remove(index);
+	Ok(())
+}
+
+/// Whether `symbol` is a valid asset or quote symbol, that is non-empty and ASCII alphanumeric.
+pub fn is_valid_symbol(symbol: &[u8]) -> bool {
+	!symbol.is_empty() && symbol.iter().all(u8::is_ascii_alphanumeric)
+}
+
+/// Find the number at the dot separated `json_path` of the JSON `body`.
+///
+/// Each segment of the path is an object key, or an index into an array. Decimals beyond the
+/// precision of `FixedU128` are truncated.
+pub fn parse_price(body: &str, json_path: &str) -> Result<FixedU128, FetchError> {
+	let json: serde_json::Value = serde_json::from_str(body).map_err(|_| FetchError::Json)?;
+	let Some(serde_json::Value::Number(price)) = json_path
+		.split('.')
+		.filter(|segment| !segment.is_empty())
+		.try_fold(&json, |value, segment| match value {
+			serde_json::Value::Array(items) =>
+				segment.parse::<usize>().ok().and_then(|index| items.get(index)),
+			_ => value.get(segment),
+		})
+	else {
+		return Err(FetchError::MissingField)
+	};
+
+	match price.as_u64() {
+		Some(integer) => FixedU128::checked_from_integer(integer),
+		// The shortest decimal representation of the float, which is the JSON literal itself
+		// unless it has more significant digits than a float holds.
+		None => price.as_f64().and_then(|float| decimal_to_fixed(&format!("{}", float))),
+	}
+	.ok_or(FetchError::MissingField)
+}
+
+/// Parse a non-negative decimal without exponent, like `155.23`, into a `FixedU128`.
+fn decimal_to_fixed(decimal: &str) -> Option<FixedU128> {
+	let (integer, fraction) = decimal.split_once('.').unwrap_or((decimal, ""));
+	if !integer.bytes().chain(fraction.bytes()).all(|digit| digit.is_ascii_digit()) {
+		return None
+	}
+
+	let mut inner = integer.parse::<u128>().ok()?.checked_mul(FixedU128::DIV)?;
+	let mut unit = FixedU128::DIV;
+	for digit in fraction.bytes() {
+		unit /= 10;
+		inner = inner.checked_add(u128::from(digit - b'0') * unit)?;
+	}
+
+	Some(FixedU128::from_inner(inner))
+}
@@ -0,0 +1,176 @@
+//! Test externalities and helpers for the offchain workers, shared by the tests of the pallets.
+
+use crate::{AssetId, FetchFailures, FixedPointNumber, FixedU128, Worker};
+use sp_core::{
+	crypto::KeyTypeId,
+	offchain::{
+		testing::{
+			OffchainState, PendingRequest, PoolState, TestOffchainExt, TestTransactionPoolExt,
+		},
+		Externalities, HttpError, HttpRequestId, HttpRequestStatus, OffchainDbExt,
+		OffchainWorkerExt, OpaqueNetworkState, OpaquePeerId, StorageKind, Timestamp,
+		TransactionPoolExt,
+	},
+};
+use sp_io::TestExternalities;
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::offchain::storage::StorageValueRef;
+use std::{collections::VecDeque, sync::Arc};
+
+/// The URL of the price of BTC in USD from the default source.
+pub const BTC_USD: &str = "https://min-api.cryptocompare.com/data/price?fsym=BTC&tsyms=USD";
+/// The URL of the price of ETH in EUR from the default source.
+pub const ETH_EUR: &str = "https://min-api.cryptocompare.com/data/price?fsym=ETH&tsyms=EUR";
+
+/// The statuses of the next HTTP responses, replacing the `200` of `TestOffchainExt`, e.g.
+/// `HttpRequestStatus::DeadlineReached` for a request timing out.
+pub type HttpStatuses = Arc<parking_lot::RwLock<VecDeque<HttpRequestStatus>>>;
+
+/// Externalities with an offchain worker, a transaction pool and a keystore, along with the
+/// states of the offchain worker and the pool and the queue of `HttpStatuses`.
+pub type OffchainExt = (
+	TestExternalities,
+	Arc<parking_lot::RwLock<OffchainState>>,
+	Arc<parking_lot::RwLock<PoolState>>,
+	HttpStatuses,
+);
+
+/// `TestOffchainExt`, answering with the `HttpStatuses` queued by the tests first.
+pub struct StatusOffchainExt {
+	inner: TestOffchainExt,
+	statuses: HttpStatuses,
+}
+
+impl Externalities for StatusOffchainExt {
+	fn is_validator(&self) -> bool {
+		self.inner.is_validator()
+	}
+
+	fn network_state(&self) -> Result<OpaqueNetworkState, ()> {
+		self.inner.network_state()
+	}
+
+	fn timestamp(&mut self) -> Timestamp {
+		self.inner.timestamp()
+	}
+
+	fn sleep_until(&mut self, deadline: Timestamp) {
+		self.inner.sleep_until(deadline)
+	}
+
+	fn random_seed(&mut self) -> [u8; 32] {
+		self.inner.random_seed()
+	}
+
+	fn http_request_start(
+		&mut self,
+		method: &str,
+		uri: &str,
+		meta: &[u8],
+	) -> Result<HttpRequestId, ()> {
+		self.inner.http_request_start(method, uri, meta)
+	}
+
+	fn http_request_add_header(
+		&mut self,
+		request_id: HttpRequestId,
+		name: &str,
+		value: &str,
+	) -> Result<(), ()> {
+		self.inner.http_request_add_header(request_id, name, value)
+	}
+
+	fn http_request_write_body(
+		&mut self,
+		request_id: HttpRequestId,
+		chunk: &[u8],
+		deadline: Option<Timestamp>,
+	) -> Result<(), HttpError> {
+		self.inner.http_request_write_body(request_id, chunk, deadline)
+	}
+
+	fn http_response_wait(
+		&mut self,
+		ids: &[HttpRequestId],
+		deadline: Option<Timestamp>,
+	) -> Vec<HttpRequestStatus> {
+		let mut statuses = self.inner.http_response_wait(ids, deadline);
+		for status in statuses.iter_mut() {
+			if let Some(queued) = self.statuses.write().pop_front() {
+				*status = queued;
+			}
+		}
+		statuses
+	}
+
+	fn http_response_headers(&mut self, request_id: HttpRequestId) -> Vec<(Vec<u8>, Vec<u8>)> {
+		self.inner.http_response_headers(request_id)
+	}
+
+	fn http_response_read_body(
+		&mut self,
+		request_id: HttpRequestId,
+		buffer: &mut [u8],
+		deadline: Option<Timestamp>,
+	) -> Result<usize, HttpError> {
+		self.inner.http_response_read_body(request_id, buffer, deadline)
+	}
+
+	fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool) {
+		self.inner.set_authorized_nodes(nodes, authorized_only)
+	}
+}
+
+/// `ext` with an offchain worker, a transaction pool and a keystore holding one `sr25519` key of
+/// `key_type`.
+pub fn with_offchain(mut ext: TestExternalities, key_type: KeyTypeId) -> OffchainExt {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	keystore.sr25519_generate_new(key_type, None).unwrap();
+
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	let statuses = HttpStatuses::default();
+	ext.register_extension(OffchainWorkerExt::new(StatusOffchainExt {
+		inner: offchain,
+		statuses: statuses.clone(),
+	}));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt::new(keystore));
+
+	(ext, offchain_state, pool_state, statuses)
+}
+
+/// The price `hundredths / 100`.
+pub fn price(hundredths: u128) -> FixedU128 {
+	FixedU128::from_rational(hundredths, 100)
+}
+
+/// The asset or quote `symbol`.
+pub fn symbol(symbol: &str) -> AssetId {
+	symbol.as_bytes().to_vec().try_into().unwrap()
+}
+
+/// Expect one GET request to `uri`, answered with a 200 and `body`.
+pub fn expect_request(state: &mut OffchainState, uri: &str, body: &[u8]) {
+	state.expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: uri.into(),
+		response: Some(body.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+/// Set `key` of the persistent offchain local storage to `value`, as `offchain_localStorageSet`.
+pub fn set_local(key: &[u8], value: &str) {
+	sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, key, value.as_bytes());
+}
+
+/// The `FetchFailures` of the pair of `asset` in `quote` kept by `worker`, or the default ones.
+pub fn fetch_failures(worker: &Worker, asset: &str, quote: &str) -> FetchFailures<u64> {
+	StorageValueRef::persistent(&worker.failures_key_of(asset.as_bytes(), quote.as_bytes()))
+		.get()
+		.unwrap()
+		.unwrap_or_default()
+}
@@ -0,0 +1,364 @@
+use super::*;
+use crate::testing::*;
+use sp_core::{crypto::KeyTypeId, offchain::HttpRequestStatus};
+use sp_io::TestExternalities;
+use std::cell::Cell;
+
+const WORKER: Worker = Worker {
+	log_target: "runtime::ocw-common",
+	url_key: b"ocw-common::url",
+	json_path_key: b"ocw-common::json-path",
+	timeout_key: b"ocw-common::timeout",
+	default_timeout: 3_000,
+	last_send_key: b"ocw-common::last-send",
+	lock_key: b"ocw-common::lock",
+	failures_key: b"ocw-common::failures",
+};
+
+thread_local! {
+	static BLOCK_NUMBER: Cell<u64> = Cell::new(1);
+}
+
+// The block number the lock expiries count from, set with `set_block_number`.
+struct TestBlockNumber;
+
+impl BlockNumberProvider for TestBlockNumber {
+	type BlockNumber = u64;
+
+	fn current_block_number() -> u64 {
+		BLOCK_NUMBER.with(Cell::get)
+	}
+}
+
+fn set_block_number(block_number: u64) {
+	BLOCK_NUMBER.with(|current| current.set(block_number));
+}
+
+fn new_offchain_ext() -> OffchainExt {
+	with_offchain(TestExternalities::default(), KeyTypeId(*b"test"))
+}
+
+fn last_send() -> Option<u64> {
+	StorageValueRef::persistent(WORKER.last_send_key).get::<u64>().unwrap()
+}
+
+// A submission returning `result`, counted in `sent`.
+fn send(
+	sent: &Cell<u32>,
+	result: Result<(), SendError>,
+) -> impl FnOnce() -> Result<(), SendError> + '_ {
+	move || {
+		sent.set(sent.get() + 1);
+		result
+	}
+}
+
+#[test]
+fn parse_price_follows_json_path() {
+	assert_eq!(parse_price(r#"{"USD": 1234.56}"#, "USD"), Ok(price(123456)));
+	assert_eq!(parse_price(r#"{"data": {"price": 42}}"#, "data.price"), Ok(price(4200)));
+	assert_eq!(
+		parse_price(r#"{"data": [{"price": 1}, {"price": 2.5}]}"#, "data.1.price"),
+		Ok(price(250))
+	);
+	assert_eq!(parse_price(r#"{"USD": "1234"}"#, "USD"), Err(FetchError::MissingField));
+	assert_eq!(parse_price(r#"{"EUR": 1234}"#, "USD"), Err(FetchError::MissingField));
+	assert_eq!(parse_price("not json", "USD"), Err(FetchError::Json));
+}
+
+#[test]
+fn parse_price_keeps_decimals() {
+	assert_eq!(
+		parse_price(r#"{"USD": 0.00000123}"#, "USD"),
+		Ok(FixedU128::from_inner(1_230_000_000_000))
+	);
+	assert_eq!(parse_price(r#"{"USD": 1.5e-3}"#, "USD"), Ok(FixedU128::from_rational(15, 10_000)));
+	assert_eq!(
+		parse_price(r#"{"USD": 18446744073709551615}"#, "USD"),
+		Ok(FixedU128::saturating_from_integer(u64::MAX))
+	);
+	assert_eq!(
+		parse_price(r#"{"USD": 1e20}"#, "USD"),
+		Ok(FixedU128::saturating_from_integer(10u128.pow(20)))
+	);
+	assert_eq!(parse_price(r#"{"USD": -1.5}"#, "USD"), Err(FetchError::MissingField));
+	assert_eq!(parse_price(r#"{"USD": 1e40}"#, "USD"), Err(FetchError::MissingField));
+}
+
+#[test]
+fn register_and_deregister_pair_check_pairs() {
+	let mut pairs = BoundedVec::<_, ConstU32<2>>::default();
+	assert_eq!(
+		register_pair(&mut pairs, symbol("BTC"), symbol("US$")),
+		Err(PairError::InvalidSymbol)
+	);
+	assert_eq!(register_pair(&mut pairs, symbol(""), symbol("USD")), Err(PairError::InvalidSymbol));
+	assert_eq!(register_pair(&mut pairs, symbol("BTC"), symbol("USD")), Ok(()));
+	assert_eq!(
+		register_pair(&mut pairs, symbol("BTC"), symbol("USD")),
+		Err(PairError::AlreadyRegistered)
+	);
+	assert_eq!(register_pair(&mut pairs, symbol("ETH"), symbol("USD")), Ok(()));
+	assert_eq!(register_pair(&mut pairs, symbol("DOT"), symbol("USD")), Err(PairError::TooMany));
+
+	assert_eq!(deregister_pair(&mut pairs, &(symbol("BTC"), symbol("USD"))), Ok(()));
+	assert_eq!(pairs.into_inner(), vec![(symbol("ETH"), symbol("USD"))]);
+	let mut pairs = BoundedVec::<_, ConstU32<2>>::default();
+	assert_eq!(
+		deregister_pair(&mut pairs, &(symbol("BTC"), symbol("USD"))),
+		Err(PairError::NotRegistered)
+	);
+}
+
+#[test]
+fn price_source_defaults_without_local_storage() {
+	let (mut ext, ..) = new_offchain_ext();
+	ext.execute_with(|| {
+		let source = WORKER.price_source();
+		assert_eq!(
+			source,
+			PriceSource {
+				url: DEFAULT_URL.into(),
+				json_path: DEFAULT_JSON_PATH.into(),
+				timeout: 3_000,
+			}
+		);
+		assert_eq!(source.for_pair("BTC", "USD"), (BTC_USD.into(), "USD".into()));
+	});
+}
+
+#[test]
+fn price_source_is_read_from_local_storage() {
+	let (mut ext, ..) = new_offchain_ext();
+	ext.execute_with(|| {
+		set_local(WORKER.url_key, "http://localhost:8000/{asset}");
+		set_local(WORKER.json_path_key, "data.{quote}");
+		set_local(WORKER.timeout_key, "250");
+
+		let source = WORKER.price_source();
+		assert_eq!(
+			source,
+			PriceSource {
+				url: "http://localhost:8000/{asset}".into(),
+				json_path: "data.{quote}".into(),
+				timeout: 250,
+			}
+		);
+		assert_eq!(
+			source.for_pair("ETH", "EUR"),
+			("http://localhost:8000/ETH".into(), "data.EUR".into())
+		);
+
+		// An invalid timeout falls back to the default.
+		set_local(WORKER.timeout_key, "soon");
+		assert_eq!(WORKER.price_source().timeout, WORKER.default_timeout);
+	});
+}
+
+#[test]
+fn fetch_price_uses_default_source() {
+	let (mut ext, offchain_state, ..) = new_offchain_ext();
+	expect_request(&mut offchain_state.write(), BTC_USD, br#"{"USD": 155.23}"#);
+
+	ext.execute_with(|| {
+		assert_eq!(WORKER.fetch_price(b"BTC", b"USD").unwrap(), price(15523));
+	});
+}
+
+#[test]
+fn fetch_price_uses_configured_source() {
+	let (mut ext, offchain_state, ..) = new_offchain_ext();
+	expect_request(
+		&mut offchain_state.write(),
+		"http://localhost:8000/price",
+		br#"{"data": {"price": 42.9}}"#,
+	);
+
+	ext.execute_with(|| {
+		set_local(WORKER.url_key, "http://localhost:8000/price");
+		set_local(WORKER.json_path_key, "data.price");
+
+		assert_eq!(WORKER.fetch_price(b"BTC", b"USD").unwrap(), price(4290));
+	});
+}
+
+#[test]
+fn fetch_price_fails_on_error_status() {
+	let (mut ext, offchain_state, _, statuses) = new_offchain_ext();
+	expect_request(&mut offchain_state.write(), BTC_USD, br#"{"Response": "Error"}"#);
+	statuses.write().push_back(HttpRequestStatus::Finished(500));
+
+	ext.execute_with(|| {
+		assert_eq!(WORKER.fetch_price(b"BTC", b"USD"), Err(FetchError::Status(500)));
+	});
+}
+
+#[test]
+fn fetch_price_fails_on_malformed_json() {
+	let (mut ext, offchain_state, ..) = new_offchain_ext();
+	expect_request(&mut offchain_state.write(), BTC_USD, br#"{"USD": 155."#);
+
+	ext.execute_with(|| {
+		assert_eq!(WORKER.fetch_price(b"BTC", b"USD"), Err(FetchError::Json));
+	});
+}
+
+#[test]
+fn fetch_price_fails_on_timeout() {
+	let (mut ext, offchain_state, _, statuses) = new_offchain_ext();
+	expect_request(&mut offchain_state.write(), BTC_USD, br#"{"USD": 155.23}"#);
+	statuses.write().push_back(HttpRequestStatus::DeadlineReached);
+
+	ext.execute_with(|| {
+		assert_eq!(
+			WORKER.fetch_price(b"BTC", b"USD"),
+			Err(FetchError::Http(http::Error::DeadlineReached))
+		);
+	});
+}
+
+#[test]
+fn run_once_sends_once_per_block() {
+	let (mut ext, ..) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		let sent = Cell::new(0);
+
+		// A failed run leaves the block to the next one.
+		WORKER.run_once::<TestBlockNumber>(
+			2,
+			send(&sent, Err(SendError::Failed("No transaction submitted"))),
+		);
+		assert_eq!(last_send(), None);
+		WORKER.run_once::<TestBlockNumber>(2, send(&sent, Err(SendError::Skipped("Too early"))));
+		assert_eq!(last_send(), None);
+		WORKER.run_once::<TestBlockNumber>(2, send(&sent, Ok(())));
+		assert_eq!(last_send(), Some(2));
+
+		// The same block imported again, e.g. on another fork, or an earlier one.
+		WORKER.run_once::<TestBlockNumber>(2, send(&sent, Ok(())));
+		WORKER.run_once::<TestBlockNumber>(1, send(&sent, Ok(())));
+		assert_eq!(sent.get(), 3);
+	});
+}
+
+#[test]
+fn run_once_skips_while_locked() {
+	let (mut ext, ..) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		set_block_number(1);
+		let sent = Cell::new(0);
+		// A run which never released the lock.
+		let mut lock = StorageLock::<BlockAndTime<TestBlockNumber>>::with_block_and_time_deadline(
+			WORKER.lock_key,
+			LOCK_BLOCK_EXPIRATION,
+			Duration::from_millis(LOCK_TIMEOUT_EXPIRATION),
+		);
+		lock.try_lock().unwrap().forget();
+
+		WORKER.run_once::<TestBlockNumber>(1, send(&sent, Ok(())));
+		assert_eq!((sent.get(), last_send()), (0, None));
+
+		// The lock expires once both its blocks and its time have passed.
+		set_block_number(5);
+		WORKER.run_once::<TestBlockNumber>(5, send(&sent, Ok(())));
+		assert_eq!(sent.get(), 0);
+		sp_io::offchain::sleep_until(
+			sp_io::offchain::timestamp().add(Duration::from_millis(LOCK_TIMEOUT_EXPIRATION + 1)),
+		);
+		WORKER.run_once::<TestBlockNumber>(5, send(&sent, Ok(())));
+		assert_eq!((sent.get(), last_send()), (1, Some(5)));
+	});
+}
+
+#[test]
+fn fetch_price_backs_off_while_it_fails() {
+	let (mut ext, offchain_state, _, statuses) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		let failures = || fetch_failures(&WORKER, "BTC", "USD");
+
+		// Retried on the next block, then after 2 blocks, then after 4 blocks.
+		for (block, status, body) in [
+			(1u64, Some(HttpRequestStatus::Finished(404)), &br#"{"USD": 155.23}"#[..]),
+			(2, None, &b"<html>Too Many Requests</html>"[..]),
+			(4, Some(HttpRequestStatus::DeadlineReached), &br#"{"USD": 155.23}"#[..]),
+		] {
+			expect_request(&mut offchain_state.write(), BTC_USD, body);
+			statuses.write().extend(status);
+			assert_eq!(
+				WORKER.fetch_price_with_backoff(block, b"BTC", b"USD"),
+				Err(SendError::Failed("Failed to fetch price"))
+			);
+		}
+		assert_eq!(
+			failures(),
+			FetchFailures {
+				consecutive: 3,
+				retry_at: 8,
+				http: 1,
+				status: 1,
+				json: 1,
+				..Default::default()
+			}
+		);
+
+		// No request before block 8, while the other pairs are fetched.
+		assert_eq!(
+			WORKER.fetch_price_with_backoff(7u64, b"BTC", b"USD"),
+			Err(SendError::Skipped("Backing off after failed fetches"))
+		);
+		expect_request(&mut offchain_state.write(), ETH_EUR, br#"{"EUR": 2001.5}"#);
+		assert_eq!(WORKER.fetch_price_with_backoff(7u64, b"ETH", b"EUR"), Ok(price(200150)));
+		assert_eq!(fetch_failures(&WORKER, "ETH", "EUR"), FetchFailures::default());
+		expect_request(&mut offchain_state.write(), BTC_USD, br#"{"USD": 155.23}"#);
+		assert_eq!(WORKER.fetch_price_with_backoff(8u64, b"BTC", b"USD"), Ok(price(15523)));
+		assert_eq!(failures().consecutive, 0);
+		assert_eq!(failures().http, 1);
+	});
+}
+
+#[test]
+fn fetch_failures_back_off_up_to_max() {
+	let mut failures = FetchFailures::<u64>::default();
+	for _ in 0..5 {
+		failures.record(FetchError::MissingField, 10);
+	}
+	assert_eq!(failures.retry_at, 26);
+	failures.record(FetchError::Utf8, 10);
+	assert_eq!(failures.retry_at, 10 + u64::from(MAX_FETCH_BACKOFF));
+	for _ in 0..40 {
+		failures.record(FetchError::Utf8, 10);
+	}
+	assert_eq!((failures.consecutive, failures.retry_at), (46, 42));
+	assert_eq!((failures.missing_field, failures.utf8), (5, 41));
+}
+
+#[test]
+fn aggregates_cover_block_window() {
+	assert_eq!(aggregation::aggregates::<u64>(&[], 8, 10), aggregation::PriceAggregates::default());
+
+	let samples = [(1u64, price(10)), (3, price(40)), (3, price(20)), (6, price(90))];
+	let aggregates = |window| aggregation::aggregates(&samples, 8, window);
+	assert_eq!(aggregates(10).mean, Some(price(40)));
+	assert_eq!(aggregates(10).median, Some(price(30)));
+	// Only the prices of blocks 2 to 8.
+	assert_eq!(aggregates(7).mean, Some(price(50)));
+	assert_eq!(aggregates(7).median, Some(price(40)));
+	assert_eq!(aggregates(1).mean, None);
+
+	// 0.20 from block 3 to 6, then 0.90 until block 8.
+	assert_eq!(aggregates(5).twap, Some(price(48)));
+	// 0.90 since block 6.
+	assert_eq!(aggregates(2).twap, Some(price(90)));
+	assert_eq!(aggregates(0).twap, None);
+}
+
+#[test]
+fn time_weighted_average_uses_latest_price_without_span() {
+	let samples = [(5u64, price(10)), (5, price(20))];
+	assert_eq!(aggregation::time_weighted_average(&samples, 3, 5), Some(price(20)));
+	assert_eq!(aggregation::time_weighted_average(&samples, 6, 6), None);
+	assert_eq!(aggregation::time_weighted_average(&samples, 3, 7), Some(price(20)));
+}
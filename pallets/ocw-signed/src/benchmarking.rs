@@ -0,0 +1,70 @@
+use crate::*;
+use frame_benchmarking::v1::{benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	traits::{EnsureOrigin, Get},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use ocw_common::MAX_SYMBOL_LEN;
+use sp_std::{vec, vec::Vec};
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+// A symbol of the longest length, distinct for each `index`.
+fn symbol(index: u32) -> AssetId {
+	let mut symbol = vec![b'A'; MAX_SYMBOL_LEN as usize];
+	for (byte, digit) in symbol.iter_mut().rev().zip(0..10) {
+		*byte = b'0' + (index / 10u32.pow(digit) % 10) as u8;
+	}
+	BoundedVec::truncate_from(symbol)
+}
+
+// Register `count` pairs, so that the pairs scanned by the calls are as many as can be.
+fn register_pairs<T: Config>(count: u32) {
+	let pairs = (0..count).map(|i| (symbol(i), symbol(i))).collect::<Vec<_>>();
+	Pairs::<T>::put(BoundedVec::truncate_from(pairs));
+}
+
+benchmarks! {
+	submit_price_signed {
+		let caller: T::AccountId = whitelisted_caller();
+		let max_pairs = T::MaxPairs::get();
+		register_pairs::<T>(max_pairs);
+		// The last pair, with a full buffer of prices to shift.
+		let (asset, quote) = (symbol(max_pairs - 1), symbol(max_pairs - 1));
+		let prices = (0 .. T::MaxPrices::get())
+			.map(|_| (frame_system::Pallet::<T>::block_number(), FixedU128::from_u32(1)))
+			.collect::<Vec<_>>();
+		Prices::<T>::insert((asset.clone(), quote.clone()), BoundedVec::truncate_from(prices));
+		let price = FixedU128::from_u32(2);
+	}: _(RawOrigin::Signed(caller.clone()), asset.clone(), quote.clone(), price)
+	verify {
+		assert_last_event::<T>(Event::NewPrice { asset, quote, price, who: caller }.into());
+	}
+
+	register_pair {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let max_pairs = T::MaxPairs::get();
+		register_pairs::<T>(max_pairs - 1);
+		let (asset, quote) = (symbol(max_pairs - 1), symbol(max_pairs - 1));
+	}: _<T::RuntimeOrigin>(origin, asset.clone(), quote.clone())
+	verify {
+		assert_last_event::<T>(Event::PairRegistered { asset, quote }.into());
+	}
+
+	deregister_pair {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		register_pairs::<T>(T::MaxPairs::get());
+		// The first pair, so that all the others are shifted.
+		let (asset, quote) = (symbol(0), symbol(0));
+	}: _<T::RuntimeOrigin>(origin, asset.clone(), quote.clone())
+	verify {
+		assert_last_event::<T>(Event::PairDeregistered { asset, quote }.into());
+	}
+
+	impl_benchmark_test_suite!(OcwSignedModule, crate::mock::new_test_ext(), crate::mock::Test);
+}
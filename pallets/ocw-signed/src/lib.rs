@@ -1,38 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// A module for offchain worker send unsigned transaction
-
-use frame_support::{
-	debug,
-	decl_module,
-	decl_storage,
-	decl_event,
-	decl_error,
-	dispatch::{DispatchResult},
-};
-use frame_system::{
-	self as system,
-	ensure_signed,
-	offchain::{
-		Signer,
-		CreateSignedTransaction,
-		SendSignedTransaction,
-		AppCrypto,
-	},
-};
-use sp_core::crypto::KeyTypeId;
-use sp_std::vec::Vec;
-use sp_runtime::{
-		offchain::{
-			http,
-			Duration,
-		},
-};
-use sp_std::prelude::*;
-// We use `alt_serde`, and Xanewok-modified `serde_json` so that we can compile the program
-//   with serde(features `std`) and alt_serde(features `no_std`).
-use alt_serde::{Deserialize, Deserializer};
-use codec::{Encode, Decode};
+/// A module for offchain worker send signed transaction
+///
+/// Governance registers the `(asset, quote)` pairs to track, and every block the offchain
+/// worker fetches the price of the next registered pair, in round-robin, from an HTTP endpoint
+/// and submits it in a signed transaction. The endpoint, the path of the price in its JSON
+/// response and the HTTP timeout are read from the offchain local storage, so they can be
+/// changed on a running node with the `offchain_localStorageSet` RPC, e.g. to point it at a
+/// local stand-in server.
+pub use pallet::*;
+pub use weights::WeightInfo;
 
 #[cfg(test)]
 mod mock;
@@ -40,25 +17,54 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-// TODO
-// type TokenPrice = u32;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
-const MAX_LEN: usize = 64; // TODO configurage
+pub mod weights;
 
-// Specifying serde path as `alt_serde`
-// ref: https://serde.rs/container-attrs.html#crate
-#[serde(crate = "alt_serde")]
-#[derive(Deserialize, Encode, Decode, Default)]
-struct PriceInfo {
-	#[serde(rename(deserialize = "USD"), deserialize_with = "de_float_to_integer")]
-    usd: u32,
-}
+use frame_system::{
+	offchain::{SendSignedTransaction, Signer},
+	pallet_prelude::BlockNumberFor,
+};
+use ocw_common::{aggregation, PairError, SendError, Worker};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::traits::UniqueSaturatedInto;
 
-pub fn de_float_to_integer<'de, D>(de: D) -> Result<u32, D::Error>
-where D: Deserializer<'de> {
-	let f: f32 = Deserialize::deserialize(de)?;
-	Ok(f as u32)
-}
+pub use ocw_common::{aggregation::PriceAggregates, AssetId, QuoteId};
+pub use sp_runtime::{FixedPointNumber, FixedU128};
+
+const LOG_TARGET: &str = "runtime::ocw-signed";
+
+/// Offchain local storage key of the URL the price is fetched from.
+///
+/// `{asset}` and `{quote}` in the URL, as well as in the JSON path, are replaced by the symbols
+/// of the pair being fetched.
+pub const URL_KEY: &[u8] = b"ocw-signed::url";
+/// Offchain local storage key of the dot separated path of the price in the JSON response.
+pub const JSON_PATH_KEY: &[u8] = b"ocw-signed::json-path";
+/// Offchain local storage key of the HTTP timeout, in milliseconds.
+pub const TIMEOUT_KEY: &[u8] = b"ocw-signed::timeout";
+
+/// Offchain local storage key of the last block a price was submitted for, SCALE encoded.
+pub const LAST_SEND_KEY: &[u8] = b"ocw-signed::last-send";
+/// Offchain local storage key of the lock held by a running offchain worker.
+pub const LOCK_KEY: &[u8] = b"ocw-signed::lock";
+/// Prefix of the offchain local storage keys of the `FetchFailures` of each pair, SCALE encoded.
+pub const FAILURES_KEY: &[u8] = b"ocw-signed::failures";
+
+pub const DEFAULT_TIMEOUT: u64 = 5_000;
+
+/// The offchain worker of the pallet.
+pub const WORKER: Worker = Worker {
+	log_target: LOG_TARGET,
+	url_key: URL_KEY,
+	json_path_key: JSON_PATH_KEY,
+	timeout_key: TIMEOUT_KEY,
+	default_timeout: DEFAULT_TIMEOUT,
+	last_send_key: LAST_SEND_KEY,
+	lock_key: LOCK_KEY,
+	failures_key: FAILURES_KEY,
+};
 
 /// Defines application identifier for crypto keys of this module.
 ///
@@ -74,172 +80,277 @@ pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"btc!");
 pub mod sr25519 {
 	use super::KEY_TYPE;
 	use sp_application_crypto::{app_crypto, sr25519};
+	use sp_runtime::{traits::Verify, MultiSignature, MultiSigner};
 
 	app_crypto!(sr25519, KEY_TYPE);
 
 	pub type AuthorityId = Public;
-}
 
-/// The pallet's configuration trait.
-pub trait Trait: system::Trait + CreateSignedTransaction<Call<Self>> {
-	/// The identifier type for an offchain worker.
-	type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+	/// Signs the price transactions with the `sr25519` keys of `KEY_TYPE`.
+	pub struct AuthId;
 
-	/// The overarching event type.
-	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
 
-	/// The overarching dispatch call type.
-	type Call: From<Call<Self>>;
-	
+	// For runtimes, like the test runtime, signing with plain `sr25519` signatures.
+	impl
+		frame_system::offchain::AppCrypto<
+			<sp_core::sr25519::Signature as Verify>::Signer,
+			sp_core::sr25519::Signature,
+		> for AuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
 }
 
-// This pallet's storage items.
-decl_storage! {
-	trait Store for Module<T: Trait> as OcwSignedModule {
-		/// A vector of recently submitted prices.
-		/// 
-		/// This is used to calculate average price, should have bounded size.
-		Prices get(fn prices): Vec<u32>;
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction},
+		pallet_prelude::*,
+	};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The pallet's configuration trait.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
+		/// The identifier type for an offchain worker.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin which may register and deregister pairs.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of registered pairs.
+		#[pallet::constant]
+		type MaxPairs: Get<u32>;
+
+		/// The number of recent prices kept for each pair.
+		#[pallet::constant]
+		type MaxPrices: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
-}
 
-// The pallet's events
-decl_event!(
-	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
-		NewPrice(u32, AccountId),
+	/// The registered pairs, fetched by the offchain worker in this order.
+	#[pallet::storage]
+	#[pallet::getter(fn pairs)]
+	pub type Pairs<T: Config> =
+		StorageValue<_, BoundedVec<(AssetId, QuoteId), T::MaxPairs>, ValueQuery>;
+
+	/// The recently submitted prices of each pair, with the block they were submitted in.
+	///
+	/// A ring buffer of the latest `MaxPrices` prices, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn prices)]
+	pub type Prices<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(AssetId, QuoteId),
+		BoundedVec<(BlockNumberFor<T>, FixedU128), T::MaxPrices>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A pair is registered.
+		PairRegistered { asset: AssetId, quote: QuoteId },
+		/// A pair and its prices are removed.
+		PairDeregistered { asset: AssetId, quote: QuoteId },
+		/// A new price of a pair was submitted.
+		NewPrice { asset: AssetId, quote: QuoteId, price: FixedU128, who: T::AccountId },
 	}
-);
 
-// The pallet's errors
-decl_error! {
-	pub enum Error for Module<T: Trait> {
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The symbol is empty or not ASCII alphanumeric.
+		InvalidSymbol,
+		/// The pair is already registered.
+		PairAlreadyRegistered,
+		/// The pair is not registered.
+		PairNotRegistered,
+		/// There are already `MaxPairs` registered pairs.
+		TooManyPairs,
 	}
-}
 
-// The pallet's dispatchable functions.
-decl_module! {
-	/// The module declaration.
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		// Initializing errors
-		// this includes information about your errors in the node's metadata.
-		// it is needed only if you are using errors in your pallet
-		type Error = Error<T>;
-
-		// Initializing events
-		// this is needed only if you are using events in your pallet
-		fn deposit_event() = default;
-
-		#[weight = 0]
-		pub fn submit_price_signed(origin, price: u32) -> DispatchResult {
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(T::MaxPrices::get() > 0, "`MaxPrices` must keep at least one price");
+		}
+
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			log::info!(target: LOG_TARGET, "Offchain worker starts running");
+
+			let Some((asset, quote)) = Self::pair_at(block_number) else {
+				log::info!(target: LOG_TARGET, "No pair registered");
+				return
+			};
+
+			WORKER.run_once::<frame_system::Pallet<T>>(block_number, || {
+				Self::fetch_price_and_send_signed(block_number, asset, quote)
+			});
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit a new price of a registered pair, signed by the offchain worker.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::submit_price_signed())]
+		pub fn submit_price_signed(
+			origin: OriginFor<T>,
+			asset: AssetId,
+			quote: QuoteId,
+			price: FixedU128,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Self::add_price(who, price);
-			
+			let pair = (asset, quote);
+			ensure!(Self::pairs().contains(&pair), Error::<T>::PairNotRegistered);
+			Self::add_price(who, pair, price);
+
 			Ok(())
 		}
-		
-		fn offchain_worker() {
-			debug::native::info!("Submit signed: Offchain working starts running");
 
-			let res = Self::fetch_price_and_send_signed();
+		/// Register a pair for the offchain worker to fetch.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::register_pair())]
+		pub fn register_pair(
+			origin: OriginFor<T>,
+			asset: AssetId,
+			quote: QuoteId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Pairs::<T>::try_mutate(|pairs| {
+				ocw_common::register_pair(pairs, asset.clone(), quote.clone())
+					.map_err(Error::<T>::from)
+			})?;
+
+			Self::deposit_event(Event::PairRegistered { asset, quote });
+			Ok(())
+		}
 
-			if let Err(e) = res {
-				debug::error!("Submit signed: Error happends: {}", e);
-			}
+		/// Stop fetching a pair and remove its prices.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::deregister_pair())]
+		pub fn deregister_pair(
+			origin: OriginFor<T>,
+			asset: AssetId,
+			quote: QuoteId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let pair = (asset, quote);
+			Pairs::<T>::try_mutate(|pairs| {
+				ocw_common::deregister_pair(pairs, &pair).map_err(Error::<T>::from)
+			})?;
+			Prices::<T>::remove(&pair);
+
+			let (asset, quote) = pair;
+			Self::deposit_event(Event::PairDeregistered { asset, quote });
+			Ok(())
 		}
 	}
 }
 
-impl<T: Trait> Module<T> {
-	fn add_price(who: T::AccountId, price: u32) {
-		debug::info!("Submit signed: Adding to the prices: {}", price);
-		Prices::mutate(|prices| {
-			if prices.len() < MAX_LEN {
-				prices.push(price);
-			} else {
-				prices[price as usize % MAX_LEN] = price;
-			}
+impl<T> From<PairError> for Error<T> {
+	fn from(error: PairError) -> Self {
+		match error {
+			PairError::InvalidSymbol => Error::InvalidSymbol,
+			PairError::AlreadyRegistered => Error::PairAlreadyRegistered,
+			PairError::NotRegistered => Error::PairNotRegistered,
+			PairError::TooMany => Error::TooManyPairs,
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The aggregates of the prices of a pair over the last `window` blocks.
+	pub fn aggregates(pair: &(AssetId, QuoteId), window: BlockNumberFor<T>) -> PriceAggregates {
+		let now = frame_system::Pallet::<T>::block_number();
+		aggregation::aggregates(&Self::prices(pair), now, window)
+	}
+
+	/// The pair to fetch at `block_number`, going round the registered pairs one per block.
+	fn pair_at(block_number: BlockNumberFor<T>) -> Option<(AssetId, QuoteId)> {
+		let pairs = Self::pairs();
+		if pairs.is_empty() {
+			return None
+		}
+		let index: u32 = (block_number % (pairs.len() as u32).into()).unique_saturated_into();
+		pairs.get(index as usize).cloned()
+	}
+
+	fn add_price(who: T::AccountId, pair: (AssetId, QuoteId), price: FixedU128) {
+		log::info!(target: LOG_TARGET, "Adding to the prices: {:?}", price);
+		let now = frame_system::Pallet::<T>::block_number();
+		Prices::<T>::mutate(&pair, |prices| {
+			// Once the buffer is full, the oldest price makes room for the new one.
+			let _ = prices.force_insert_keep_right(prices.len(), (now, price));
 		});
 
-		Self::deposit_event(RawEvent::NewPrice(price, who));
+		let (asset, quote) = pair;
+		Self::deposit_event(Event::NewPrice { asset, quote, price, who });
 	}
 
-	fn fetch_price_and_send_signed() -> Result<(), &'static str> {
+	fn fetch_price_and_send_signed(
+		block_number: BlockNumberFor<T>,
+		asset: AssetId,
+		quote: QuoteId,
+	) -> Result<(), SendError> {
 		let signer = Signer::<T, T::AuthorityId>::all_accounts();
 		if !signer.can_sign() {
-			return Err(
-				"No local accounts available. Consider adding one via `author_insertKey` RPC."
-			)?
+			return Err(SendError::Failed(
+				"No local accounts available. Consider adding one via `author_insertKey` RPC.",
+			));
 		}
 
 		// Make an external HTTP request to fetch the current price.
 		// Note this call will block until response is received.
-		let price = Self::fetch_price().map_err(|_| "Submit signed: Failed to fetch price")?;
+		let price = WORKER.fetch_price_with_backoff(block_number, &asset, &quote)?;
 
 		// Using `send_signed_transaction` associated type we create and submit a transaction
 		// representing the call, we've just created.
 		// Submit signed will return a vector of results for all accounts that were found in the
 		// local keystore with expected `KEY_TYPE`.
-		let results = signer.send_signed_transaction(
-			|_account| {
-				// Received price is wrapped into a call to `submit_price` public function of this pallet.
-				// This means that the transaction, when executed, will simply call that function passing
-				// `price` as an argument.
-				Call::submit_price_signed(price)
-			}
-		);
+		let results = signer.send_signed_transaction(|_account| {
+			// Received price is wrapped into a call to `submit_price_signed` public function of
+			// this pallet. This means that the transaction, when executed, will simply call that
+			// function passing the pair and `price` as arguments.
+			Call::submit_price_signed { asset: asset.clone(), quote: quote.clone(), price }
+		});
 
 		for (acc, res) in &results {
 			match res {
-				Ok(()) => debug::info!("Submit signed: [{:?}] Submitted price of {} cents", acc.id, price),
-				Err(e) => debug::error!("Submit signed: [{:?}] Failed to submit transcation, {:?}", acc.id, e),
+				Ok(()) =>
+					log::info!(target: LOG_TARGET, "[{:?}] Submitted price {:?}", acc.id, price),
+				Err(e) => log::error!(
+					target: LOG_TARGET,
+					"[{:?}] Failed to submit transaction: {:?}",
+					acc.id,
+					e
+				),
 			}
 		}
 
-		Ok(())
-	}
-
-	fn fetch_price() -> Result<u32, http::Error> {
-		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(5000));
-		// Initiate an external HTTP GET request.
-		// This is using high-level wrappers from `sp_runtime`, for the low-level calls that
-		// you can find in `sp_io`. The API is trying to be similar to `reqwest`, but
-		// since we are running in a custom WASM execution environment we can't simply
-		// import the library here.
-		let request = http::Request::get(
-			"https://min-api.cryptocompare.com/data/price?fsym=BTC&tsyms=USD"
-		);
-		// We set the deadline for sending of the request, note that awaiting response can
-		// have a separate deadline. Next we send the request, before that it's also possible
-		// to alter request headers or stream body content in case of non-GET requests.
-		let pending = request
-			.deadline(deadline)
-			.send()
-			.map_err(|_| http::Error::IoError)?;
-		// The request is already being processed by the host, we are free to do anything
-		// else in the worker (we can send multiple concurrent requests too).
-		// At some point however we probably want to check the response though,
-		// so we can block current thread and wait for it to finish.
-		// Note that since the request is being driven by the host, we don't have to wait
-		// for the request to have it complete, we will just not read the response.
-		let response = pending.try_wait(deadline)
-			.map_err(|_| http::Error::DeadlineReached)??;
-
-		if response.code != 200 {
-			debug::warn!("Submit signed: Unexpected status code: {}", response.code);
-			return Err(http::Error::Unknown);
+		if results.iter().all(|(_, res)| res.is_err()) {
+			return Err(SendError::Failed("No transaction submitted"))
 		}
 
-		let body = response.body().collect::<Vec<u8>>();
-
-		let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
-			debug::warn!("Not UTF8 body");
-			http::Error::Unknown
-		})?;
-
-		let price_info: PriceInfo = serde_json::from_str(&body_str).unwrap();
-		debug::warn!("Submit Signed: Got price: {} cents", price_info.usd);
-
-		Ok(price_info.usd)
+		Ok(())
 	}
-
 }
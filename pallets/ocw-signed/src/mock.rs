@@ -1,55 +1,102 @@
-// // Creating mock runtime here
-
-// use crate::{Module, Trait};
-// use sp_core::H256;
-// use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
-// use sp_runtime::{
-// 	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
-// };
-
-// impl_outer_origin! {
-// 	pub enum Origin for Test {}
-// }
-
-// // For testing the pallet, we construct most of a mock runtime. This means
-// // first constructing a configuration type (`Test`) which `impl`s each of the
-// // configuration traits of pallets we want to use.
-// #[derive(Clone, Eq, PartialEq)]
-// pub struct Test;
-// parameter_types! {
-// 	pub const BlockHashCount: u64 = 250;
-// 	pub const MaximumBlockWeight: Weight = 1024;
-// 	pub const MaximumBlockLength: u32 = 2 * 1024;
-// 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
-// }
-// impl frame_system::Trait for Test {
-// 	type Origin = Origin;
-// 	type Call = ();
-// 	type Index = u64;
-// 	type BlockNumber = u64;
-// 	type Hash = H256;
-// 	type Hashing = BlakeTwo256;
-// 	type AccountId = u64;
-// 	type Lookup = IdentityLookup<Self::AccountId>;
-// 	type Header = Header;
-// 	type Event = ();
-// 	type BlockHashCount = BlockHashCount;
-// 	type MaximumBlockWeight = MaximumBlockWeight;
-// 	type MaximumBlockLength = MaximumBlockLength;
-// 	type AvailableBlockRatio = AvailableBlockRatio;
-// 	type Version = ();
-// 	type ModuleToIndex = ();
-// 	type AccountData = ();
-// 	type OnNewAccount = ();
-// 	type OnKilledAccount = ();
-// }
-// impl Trait for Test {
-// 	type Event = ();
-// }
-// pub type TemplateModule = Module<Test>;
-
-// // This function basically just builds a genesis storage key/value store according to
-// // our desired mockup.
-// pub fn new_test_ext() -> sp_io::TestExternalities {
-// 	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
-// }
+// Creating mock runtime here
+
+use crate as pallet_ocw_signed;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64};
+use frame_system::EnsureRoot;
+use ocw_common::testing::{with_offchain, OffchainExt};
+use sp_core::{sr25519::Signature, H256};
+use sp_runtime::{
+	testing::TestXt,
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
+	BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		OcwSignedModule: pallet_ocw_signed,
+	}
+);
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: <Signature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+impl pallet_ocw_signed::Config for Test {
+	type AuthorityId = pallet_ocw_signed::sr25519::AuthId;
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxPairs = ConstU32<3>;
+	type MaxPrices = ConstU32<4>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// `new_test_ext` with an offchain worker, a transaction pool and a keystore holding one key of
+/// the pallet.
+pub fn new_offchain_ext() -> OffchainExt {
+	with_offchain(new_test_ext(), pallet_ocw_signed::KEY_TYPE)
+}
@@ -1,5 +1,281 @@
-// // Tests to be written here
+// Tests to be written here
 
-// use crate::{Error, mock::*};
-// use frame_support::{assert_ok, assert_noop};
+use super::*;
+use crate::{mock::*, Error};
+use codec::Decode;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use ocw_common::{
+	testing::{expect_request, fetch_failures, price, symbol, BTC_USD, ETH_EUR},
+	FetchFailures,
+};
+use sp_core::offchain::HttpRequestStatus;
+use sp_runtime::{offchain::storage::StorageValueRef, traits::BadOrigin};
 
+fn register(asset: &str, quote: &str) {
+	assert_ok!(OcwSignedModule::register_pair(RuntimeOrigin::root(), symbol(asset), symbol(quote)));
+}
+
+#[test]
+fn submit_price_signed_works() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		let who = AccountId::from_raw([1; 32]);
+		assert_ok!(OcwSignedModule::submit_price_signed(
+			RuntimeOrigin::signed(who),
+			symbol("BTC"),
+			symbol("USD"),
+			price(4200)
+		));
+
+		assert_eq!(OcwSignedModule::prices((symbol("BTC"), symbol("USD"))), vec![(1, price(4200))]);
+		assert!(OcwSignedModule::prices((symbol("ETH"), symbol("USD"))).is_empty());
+		System::assert_last_event(
+			Event::<Test>::NewPrice {
+				asset: symbol("BTC"),
+				quote: symbol("USD"),
+				price: price(4200),
+				who,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn submit_price_signed_requires_registered_pair() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		let who = AccountId::from_raw([1; 32]);
+		assert_noop!(
+			OcwSignedModule::submit_price_signed(
+				RuntimeOrigin::signed(who),
+				symbol("USD"),
+				symbol("BTC"),
+				price(4200)
+			),
+			Error::<Test>::PairNotRegistered
+		);
+	});
+}
+
+// Submit `price` of BTC in USD at block `at`.
+fn submit_at(at: u64, price: FixedU128) {
+	System::set_block_number(at);
+	assert_ok!(OcwSignedModule::submit_price_signed(
+		RuntimeOrigin::signed(AccountId::from_raw([1; 32])),
+		symbol("BTC"),
+		symbol("USD"),
+		price
+	));
+}
+
+#[test]
+fn prices_are_a_ring_buffer() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		for at in 1..=4 {
+			submit_at(at, price(at.into()));
+		}
+		submit_at(5, price(5));
+
+		assert_eq!(
+			OcwSignedModule::prices((symbol("BTC"), symbol("USD"))),
+			vec![(2, price(2)), (3, price(3)), (4, price(4)), (5, price(5))]
+		);
+	});
+}
+
+#[test]
+fn aggregates_cover_block_window() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		let pair = (symbol("BTC"), symbol("USD"));
+		assert_eq!(OcwSignedModule::aggregates(&pair, 10), PriceAggregates::default());
+
+		submit_at(1, price(10));
+		submit_at(3, price(40));
+		submit_at(3, price(20));
+		submit_at(6, price(90));
+		System::set_block_number(8);
+
+		assert_eq!(OcwSignedModule::aggregates(&pair, 10).mean, Some(price(40)));
+		assert_eq!(OcwSignedModule::aggregates(&pair, 10).median, Some(price(30)));
+		// Only the prices of blocks 2 to 8.
+		assert_eq!(OcwSignedModule::aggregates(&pair, 7).mean, Some(price(50)));
+		assert_eq!(OcwSignedModule::aggregates(&pair, 7).median, Some(price(40)));
+		assert_eq!(OcwSignedModule::aggregates(&pair, 1).mean, None);
+
+		// 0.20 from block 3 to 6, then 0.90 until block 8.
+		assert_eq!(OcwSignedModule::aggregates(&pair, 5).twap, Some(price(48)));
+		// 0.90 since block 6.
+		assert_eq!(OcwSignedModule::aggregates(&pair, 2).twap, Some(price(90)));
+		assert_eq!(OcwSignedModule::aggregates(&pair, 0).twap, None);
+	});
+}
+
+#[test]
+fn register_pair_works() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId::from_raw([1; 32]);
+		assert_noop!(
+			OcwSignedModule::register_pair(
+				RuntimeOrigin::signed(who),
+				symbol("BTC"),
+				symbol("USD")
+			),
+			BadOrigin
+		);
+
+		register("BTC", "USD");
+		System::assert_last_event(
+			Event::<Test>::PairRegistered { asset: symbol("BTC"), quote: symbol("USD") }.into(),
+		);
+		register("ETH", "USD");
+		assert_eq!(
+			OcwSignedModule::pairs().into_inner(),
+			vec![(symbol("BTC"), symbol("USD")), (symbol("ETH"), symbol("USD"))]
+		);
+
+		assert_noop!(
+			OcwSignedModule::register_pair(RuntimeOrigin::root(), symbol("BTC"), symbol("USD")),
+			Error::<Test>::PairAlreadyRegistered
+		);
+		assert_noop!(
+			OcwSignedModule::register_pair(RuntimeOrigin::root(), symbol("BTC"), symbol("US$")),
+			Error::<Test>::InvalidSymbol
+		);
+		assert_noop!(
+			OcwSignedModule::register_pair(RuntimeOrigin::root(), symbol(""), symbol("USD")),
+			Error::<Test>::InvalidSymbol
+		);
+
+		register("DOT", "USD");
+		assert_noop!(
+			OcwSignedModule::register_pair(RuntimeOrigin::root(), symbol("KSM"), symbol("USD")),
+			Error::<Test>::TooManyPairs
+		);
+	});
+}
+
+#[test]
+fn deregister_pair_removes_prices() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		register("ETH", "USD");
+		let who = AccountId::from_raw([1; 32]);
+		assert_ok!(OcwSignedModule::submit_price_signed(
+			RuntimeOrigin::signed(who),
+			symbol("BTC"),
+			symbol("USD"),
+			price(4200)
+		));
+
+		assert_noop!(
+			OcwSignedModule::deregister_pair(
+				RuntimeOrigin::signed(who),
+				symbol("BTC"),
+				symbol("USD")
+			),
+			BadOrigin
+		);
+		assert_ok!(OcwSignedModule::deregister_pair(
+			RuntimeOrigin::root(),
+			symbol("BTC"),
+			symbol("USD")
+		));
+		System::assert_last_event(
+			Event::<Test>::PairDeregistered { asset: symbol("BTC"), quote: symbol("USD") }.into(),
+		);
+		assert_eq!(OcwSignedModule::pairs().into_inner(), vec![(symbol("ETH"), symbol("USD"))]);
+		assert!(OcwSignedModule::prices((symbol("BTC"), symbol("USD"))).is_empty());
+
+		assert_noop!(
+			OcwSignedModule::deregister_pair(RuntimeOrigin::root(), symbol("BTC"), symbol("USD")),
+			Error::<Test>::PairNotRegistered
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_fetches_pairs_in_round_robin() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		register("ETH", "EUR");
+
+		for (block, asset, quote, uri, body, hundredths) in [
+			(1, "ETH", "EUR", ETH_EUR, &br#"{"EUR": 3012.5}"#[..], 301250),
+			(2, "BTC", "USD", BTC_USD, &br#"{"USD": 155.23}"#[..], 15523),
+			(3, "ETH", "EUR", ETH_EUR, &br#"{"EUR": 3013}"#[..], 301300),
+		] {
+			expect_request(&mut offchain_state.write(), uri, body);
+			OcwSignedModule::offchain_worker(block);
+
+			let tx = pool_state.write().transactions.pop().unwrap();
+			assert!(pool_state.read().transactions.is_empty());
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			assert!(tx.signature.is_some());
+			assert_eq!(
+				tx.call,
+				RuntimeCall::OcwSignedModule(Call::submit_price_signed {
+					asset: symbol(asset),
+					quote: symbol(quote),
+					price: price(hundredths)
+				})
+			);
+		}
+	});
+}
+
+#[test]
+fn offchain_worker_without_pairs_does_nothing() {
+	let (mut ext, _, pool_state, _) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		OcwSignedModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+// Run the offchain worker at block 1, with the request of the price of BTC in USD answered by
+// `body` and `status`, if any, and return the `FetchFailures` of the pair once it is checked
+// that nothing was submitted.
+fn failed_fetch(status: Option<HttpRequestStatus>, body: &[u8]) -> FetchFailures<u64> {
+	let (mut ext, offchain_state, pool_state, statuses) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		expect_request(&mut offchain_state.write(), BTC_USD, body);
+		statuses.write().extend(status);
+		OcwSignedModule::offchain_worker(1);
+
+		assert!(pool_state.read().transactions.is_empty());
+		assert_eq!(StorageValueRef::persistent(LAST_SEND_KEY).get::<u64>().unwrap(), None);
+		fetch_failures(&WORKER, "BTC", "USD")
+	})
+}
+
+#[test]
+fn offchain_worker_backs_off_after_error_status() {
+	assert_eq!(
+		failed_fetch(Some(HttpRequestStatus::Finished(500)), br#"{"USD": 155.23}"#),
+		FetchFailures { consecutive: 1, retry_at: 2, status: 1, ..Default::default() }
+	);
+}
+
+#[test]
+fn offchain_worker_backs_off_after_malformed_json() {
+	assert_eq!(
+		failed_fetch(None, br#"{"USD": 155.23"#),
+		FetchFailures { consecutive: 1, retry_at: 2, json: 1, ..Default::default() }
+	);
+}
+
+#[test]
+fn offchain_worker_backs_off_after_timeout() {
+	assert_eq!(
+		failed_fetch(Some(HttpRequestStatus::DeadlineReached), br#"{"USD": 155.23}"#),
+		FetchFailures { consecutive: 1, retry_at: 2, http: 1, ..Default::default() }
+	);
+}
@@ -0,0 +1,77 @@
+//! Weights for pallet_ocw_signed
+//!
+//! These are estimates counted from the storage accesses of each call, not benchmark results.
+//! Regenerate this file with `benchmark pallet --pallet pallet_ocw_signed` before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_ocw_signed.
+pub trait WeightInfo {
+	fn submit_price_signed() -> Weight;
+	fn register_pair() -> Weight;
+	fn deregister_pair() -> Weight;
+}
+
+/// Weights for pallet_ocw_signed using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `OcwSignedModule::Pairs` (r:1 w:0)
+	/// Proof: `OcwSignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwSignedModule::Prices` (r:1 w:1)
+	/// Proof: `OcwSignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	fn submit_price_signed() -> Weight {
+		Weight::from_parts(21_000_000, 4797)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwSignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwSignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	fn register_pair() -> Weight {
+		Weight::from_parts(13_000_000, 2030)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwSignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwSignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwSignedModule::Prices` (r:0 w:1)
+	/// Proof: `OcwSignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	fn deregister_pair() -> Weight {
+		Weight::from_parts(16_000_000, 2030)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: `OcwSignedModule::Pairs` (r:1 w:0)
+	/// Proof: `OcwSignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwSignedModule::Prices` (r:1 w:1)
+	/// Proof: `OcwSignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	fn submit_price_signed() -> Weight {
+		Weight::from_parts(21_000_000, 4797)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwSignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwSignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	fn register_pair() -> Weight {
+		Weight::from_parts(13_000_000, 2030)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwSignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwSignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwSignedModule::Prices` (r:0 w:1)
+	/// Proof: `OcwSignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	fn deregister_pair() -> Weight {
+		Weight::from_parts(16_000_000, 2030)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -1,224 +1,522 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 /// A module for offchain worker send unsigned transaction
+///
+/// Governance registers the `(asset, quote)` pairs to track, and once the `UnsignedInterval`
+/// after the last price has passed, the offchain worker fetches the price of the next
+/// registered pair, in round-robin, from an HTTP endpoint and submits it in an unsigned
+/// transaction, with a payload signed by each authority key in the keystore. Only the payloads
+/// of the authorities managed by governance are accepted. The endpoint, the path of the price
+/// in its JSON response and the HTTP timeout are read from the offchain local storage, so they
+/// can be changed on a running node with the `offchain_localStorageSet` RPC, e.g. to point it
+/// at a local stand-in server.
+pub use pallet::*;
+pub use weights::WeightInfo;
 
-use frame_support::{
-	debug,
-	decl_module,
-	decl_storage,
-	decl_event,
-	decl_error,
-	dispatch::{DispatchResult},
-};
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use codec::{Decode, Encode};
 use frame_system::{
-	self as system,
-	ensure_none,
-	offchain::{
-		SubmitTransaction,
-		SendTransactionTypes,
+	offchain::{AppCrypto, SendUnsignedTransaction, SignedPayload, Signer, SigningTypes},
+	pallet_prelude::BlockNumberFor,
+};
+use ocw_common::{aggregation, PairError, SendError, Worker};
+use scale_info::TypeInfo;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	offchain::storage::StorageValueRef,
+	traits::{IdentifyAccount, Saturating},
+	transaction_validity::{
+		InvalidTransaction, TransactionLongevity, TransactionPriority, TransactionSource,
+		TransactionValidity, ValidTransaction,
 	},
+	RuntimeDebug,
 };
 use sp_std::vec::Vec;
-use sp_runtime::{
-		offchain::{
-			http,
-			Duration,
-		},
-		transaction_validity::{
-			InvalidTransaction,
-			ValidTransaction,
-			TransactionValidity,
-			TransactionSource,
-		},
+
+pub use ocw_common::{aggregation::PriceAggregates, AssetId, QuoteId};
+pub use sp_runtime::{FixedPointNumber, FixedU128};
+
+const LOG_TARGET: &str = "runtime::ocw-unsigned";
+
+/// Offchain local storage key of the URL the price is fetched from.
+///
+/// `{asset}` and `{quote}` in the URL, as well as in the JSON path, are replaced by the symbols
+/// of the pair being fetched.
+pub const URL_KEY: &[u8] = b"ocw-unsigned::url";
+/// Offchain local storage key of the dot separated path of the price in the JSON response.
+pub const JSON_PATH_KEY: &[u8] = b"ocw-unsigned::json-path";
+/// Offchain local storage key of the HTTP timeout, in milliseconds.
+pub const TIMEOUT_KEY: &[u8] = b"ocw-unsigned::timeout";
+
+/// Offchain local storage key of the last block a price was submitted for, SCALE encoded.
+pub const LAST_SEND_KEY: &[u8] = b"ocw-unsigned::last-send";
+/// Offchain local storage key of the lock held by a running offchain worker.
+pub const LOCK_KEY: &[u8] = b"ocw-unsigned::lock";
+/// Prefix of the offchain local storage keys of the `FetchFailures` of each pair, SCALE encoded.
+pub const FAILURES_KEY: &[u8] = b"ocw-unsigned::failures";
+/// Offchain local storage key of the position in `Pairs` of the pair to fetch next, modulo the
+/// number of pairs, SCALE encoded.
+pub const NEXT_PAIR_KEY: &[u8] = b"ocw-unsigned::next-pair";
+
+pub const DEFAULT_TIMEOUT: u64 = 2_000;
+
+/// The offchain worker of the pallet.
+pub const WORKER: Worker = Worker {
+	log_target: LOG_TARGET,
+	url_key: URL_KEY,
+	json_path_key: JSON_PATH_KEY,
+	timeout_key: TIMEOUT_KEY,
+	default_timeout: DEFAULT_TIMEOUT,
+	last_send_key: LAST_SEND_KEY,
+	lock_key: LOCK_KEY,
+	failures_key: FAILURES_KEY,
 };
-use codec::{Encode, Decode};
-use sp_std::prelude::*;
-// We use `alt_serde`, and Xanewok-modified `serde_json` so that we can compile the program
-//   with serde(features `std`) and alt_serde(features `no_std`).
-use alt_serde::{Deserialize, Deserializer};
 
-#[cfg(test)]
-mod mock;
+/// Defines application identifier for crypto keys of this module.
+///
+/// The offchain worker signs the price payloads with the keys of this type in the keystore,
+/// they can be inserted with the `author_insertKey` RPC.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"ocwu");
+/// The `sr25519` crypto type wrappers of the keys of `KEY_TYPE`.
+pub mod sr25519 {
+	use super::KEY_TYPE;
+	use sp_application_crypto::{app_crypto, sr25519};
+	use sp_runtime::{traits::Verify, MultiSignature, MultiSigner};
 
-#[cfg(test)]
-mod tests;
+	app_crypto!(sr25519, KEY_TYPE);
 
-// TODO
-// type TokenPrice = u32;
+	pub type AuthorityId = Public;
 
-const MAX_LEN: usize = 64; // TODO configurage
+	/// Signs the price payloads with the `sr25519` keys of `KEY_TYPE`.
+	pub struct AuthId;
 
-// Specifying serde path as `alt_serde`
-// ref: https://serde.rs/container-attrs.html#crate
-#[serde(crate = "alt_serde")]
-#[derive(Deserialize, Encode, Decode, Default)]
-struct PriceInfo {
-	#[serde(rename(deserialize = "USD"), deserialize_with = "de_float_to_integer")]
-    usd: u32,
-}
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
 
-pub fn de_float_to_integer<'de, D>(de: D) -> Result<u32, D::Error>
-where D: Deserializer<'de> {
-	let f: f32 = Deserialize::deserialize(de)?;
-	Ok(f as u32)
+	// For runtimes, like the test runtime, signing with plain `sr25519` signatures.
+	impl
+		frame_system::offchain::AppCrypto<
+			<sp_core::sr25519::Signature as Verify>::Signer,
+			sp_core::sr25519::Signature,
+		> for AuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
 }
 
-/// The pallet's configuration trait.
-pub trait Trait: system::Trait + SendTransactionTypes<Call<Self>> {
-	/// The overarching event type.
-	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
-
-	/// The overarching dispatch call type.
-	type Call: From<Call<Self>>;
-	
+/// A price of a pair, signed by an authority.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PricePayload<Public, BlockNumber> {
+	/// The key of the authority signing the payload.
+	pub public: Public,
+	pub asset: AssetId,
+	pub quote: QuoteId,
+	pub price: FixedU128,
+	/// The block the price was fetched at.
+	pub block_number: BlockNumber,
 }
 
-// This pallet's storage items.
-decl_storage! {
-	trait Store for Module<T: Trait> as OcwUnsignedModule {
-		/// A vector of recently submitted prices.
-		/// 
-		/// This is used to calculate average price, should have bounded size.
-		Prices get(fn prices): Vec<u32>;
+impl<T: SigningTypes> SignedPayload<T> for PricePayload<T::Public, BlockNumberFor<T>> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
 	}
 }
 
-// The pallet's events
-decl_event!(
-	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
-		NewPrice(u32, AccountId),
+/// The price payload of the runtime `T`.
+pub type PricePayloadOf<T> = PricePayload<<T as SigningTypes>::Public, BlockNumberFor<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::{offchain::SendTransactionTypes, pallet_prelude::*};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The pallet's configuration trait.
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + SendTransactionTypes<Call<Self>> + SigningTypes
+	{
+		/// The identifier type for an offchain worker.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin which may register and deregister pairs, and manage the authorities.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of registered pairs.
+		#[pallet::constant]
+		type MaxPairs: Get<u32>;
+
+		/// The number of recent prices kept for each pair.
+		#[pallet::constant]
+		type MaxPrices: Get<u32>;
+
+		/// The maximum number of authorities.
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+
+		/// The number of blocks to wait after a price is submitted before accepting the next one.
+		#[pallet::constant]
+		type UnsignedInterval: Get<BlockNumberFor<Self>>;
+
+		/// The priority of the unsigned price transactions in the transaction pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// The number of blocks an unsigned price transaction stays valid in the transaction pool.
+		#[pallet::constant]
+		type UnsignedLongevity: Get<TransactionLongevity>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
-);
 
-// The pallet's errors
-decl_error! {
-	pub enum Error for Module<T: Trait> {
-		ParseError,
+	/// The first block whose signed price payloads are accepted.
+	///
+	/// Moved `UnsignedInterval` blocks ahead every time a price is submitted, so the offchain
+	/// workers do not flood the transaction pool.
+	#[pallet::storage]
+	#[pallet::getter(fn next_unsigned_at)]
+	pub type NextUnsignedAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// The accounts whose signed price payloads are accepted.
+	#[pallet::storage]
+	#[pallet::getter(fn authorities)]
+	pub type Authorities<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxAuthorities>, ValueQuery>;
+
+	/// The registered pairs, fetched by the offchain worker in this order.
+	#[pallet::storage]
+	#[pallet::getter(fn pairs)]
+	pub type Pairs<T: Config> =
+		StorageValue<_, BoundedVec<(AssetId, QuoteId), T::MaxPairs>, ValueQuery>;
+
+	/// The recently submitted prices of each pair, with the block they were submitted in.
+	///
+	/// A ring buffer of the latest `MaxPrices` prices, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn prices)]
+	pub type Prices<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(AssetId, QuoteId),
+		BoundedVec<(BlockNumberFor<T>, FixedU128), T::MaxPrices>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A pair is registered.
+		PairRegistered { asset: AssetId, quote: QuoteId },
+		/// A pair and its prices are removed.
+		PairDeregistered { asset: AssetId, quote: QuoteId },
+		/// A new price of a pair was submitted by an authority.
+		NewPrice { asset: AssetId, quote: QuoteId, price: FixedU128, who: T::AccountId },
+		/// An account may submit prices.
+		AuthorityAdded { who: T::AccountId },
+		/// An account may no longer submit prices.
+		AuthorityRemoved { who: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The symbol is empty or not ASCII alphanumeric.
+		InvalidSymbol,
+		/// The pair is already registered.
+		PairAlreadyRegistered,
+		/// The pair is not registered.
+		PairNotRegistered,
+		/// There are already `MaxPairs` registered pairs.
+		TooManyPairs,
+		/// The account is already an authority.
+		AlreadyAuthority,
+		/// The account is not an authority.
+		NotAuthority,
+		/// There are already `MaxAuthorities` authorities.
+		TooManyAuthorities,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(T::MaxPrices::get() > 0, "`MaxPrices` must keep at least one price");
+		}
+
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			log::info!(target: LOG_TARGET, "Offchain worker starts running");
+
+			if Self::pairs().is_empty() {
+				log::info!(target: LOG_TARGET, "No pair registered");
+				return
+			}
+
+			WORKER.run_once::<frame_system::Pallet<T>>(block_number, || {
+				Self::fetch_price_and_send_unsigned(block_number)
+			});
+		}
 	}
-}
 
-// The pallet's dispatchable functions.
-decl_module! {
-	/// The module declaration.
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		// Initializing errors
-		// this includes information about your errors in the node's metadata.
-		// it is needed only if you are using errors in your pallet
-		type Error = Error<T>;
-
-		// Initializing events
-		// this is needed only if you are using events in your pallet
-		fn deposit_event() = default;
-
-		#[weight = 0]
-		pub fn submit_price_unsigned(origin, price: u32) -> DispatchResult {
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a pair for the offchain worker to fetch.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::register_pair())]
+		pub fn register_pair(
+			origin: OriginFor<T>,
+			asset: AssetId,
+			quote: QuoteId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Pairs::<T>::try_mutate(|pairs| {
+				ocw_common::register_pair(pairs, asset.clone(), quote.clone())
+					.map_err(Error::<T>::from)
+			})?;
+
+			Self::deposit_event(Event::PairRegistered { asset, quote });
+			Ok(())
+		}
+
+		/// Stop fetching a pair and remove its prices.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::deregister_pair())]
+		pub fn deregister_pair(
+			origin: OriginFor<T>,
+			asset: AssetId,
+			quote: QuoteId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let pair = (asset, quote);
+			Pairs::<T>::try_mutate(|pairs| {
+				ocw_common::deregister_pair(pairs, &pair).map_err(Error::<T>::from)
+			})?;
+			Prices::<T>::remove(&pair);
+
+			let (asset, quote) = pair;
+			Self::deposit_event(Event::PairDeregistered { asset, quote });
+			Ok(())
+		}
+
+		/// Submit a new price of a registered pair, from the offchain worker.
+		///
+		/// The payload is signed by an authority, which `validate_unsigned` checks.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::submit_price_unsigned_with_signed_payload())]
+		pub fn submit_price_unsigned_with_signed_payload(
+			origin: OriginFor<T>,
+			price_payload: PricePayloadOf<T>,
+			_signature: T::Signature,
+		) -> DispatchResult {
 			// This ensures that the function can only be called via unsigned transaction.
 			ensure_none(origin)?;
+			let PricePayload { public, asset, quote, price, .. } = price_payload;
+			let pair = (asset, quote);
+			ensure!(Self::pairs().contains(&pair), Error::<T>::PairNotRegistered);
+			Self::add_price(public.into_account(), pair, price);
 
-			// Add the price to the onchain storage, but mark it as coming from an empty address.
-			Self::add_price(Default::default(), price);
+			let now = frame_system::Pallet::<T>::block_number();
+			// The payloads the other authorities signed before the end of the interval are stale
+			// from here on, so one price is accepted per interval.
+			NextUnsignedAt::<T>::put(now.saturating_add(T::UnsignedInterval::get()));
 
 			Ok(())
 		}
-		
-		fn offchain_worker() {
-			debug::native::info!("Offchain working starts running");
 
-			let res = Self::fetch_price_and_send_unsigned();
+		/// Accept the signed price payloads of `who`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::add_authority())]
+		pub fn add_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
 
-			if let Err(e) = res {
-				debug::error!("Error happends: {}", e);
-			}
+			Authorities::<T>::try_mutate(|authorities| {
+				ensure!(!authorities.contains(&who), Error::<T>::AlreadyAuthority);
+				authorities.try_push(who.clone()).map_err(|_| Error::<T>::TooManyAuthorities)
+			})?;
+
+			Self::deposit_event(Event::AuthorityAdded { who });
+			Ok(())
 		}
-	}
-}
 
-impl<T: Trait> Module<T> {
-	fn add_price(who: T::AccountId, price: u32) {
-		debug::info!("Adding to the prices: {}", price);
-		Prices::mutate(|prices| {
-			if prices.len() < MAX_LEN {
-				prices.push(price);
-			} else {
-				prices[price as usize % MAX_LEN] = price;
-			}
-		});
+		/// Stop accepting the signed price payloads of `who`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::remove_authority())]
+		pub fn remove_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Authorities::<T>::try_mutate(|authorities| {
+				let index =
+					authorities.iter().position(|a| a == &who).ok_or(Error::<T>::NotAuthority)?;
+				authorities.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
 
-		Self::deposit_event(RawEvent::NewPrice(price, who));
+			Self::deposit_event(Event::AuthorityRemoved { who });
+			Ok(())
+		}
 	}
 
-	fn fetch_price_and_send_unsigned() -> Result<(), &'static str> {
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
 
-		let price = Self::fetch_price().map_err(|_| "Failed to fetch price")?;
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::submit_price_unsigned_with_signed_payload { price_payload, signature } = call
+			else {
+				return InvalidTransaction::Call.into()
+			};
 
-		let call = Call::submit_price_unsigned(price);
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(price_payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			let who = price_payload.public.clone().into_account();
+			if !Self::authorities().contains(&who) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			let pair = (price_payload.asset.clone(), price_payload.quote.clone());
+			if !Self::pairs().contains(&pair) {
+				return InvalidTransaction::Call.into()
+			}
 
-		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
-			.map_err(|()| "Unable to submit unsigned transaction")?;
+			// Reject the prices fetched before the end of the interval, or in a block not
+			// imported yet.
+			if price_payload.block_number < Self::next_unsigned_at() {
+				return InvalidTransaction::Stale.into()
+			}
+			if price_payload.block_number > frame_system::Pallet::<T>::block_number() {
+				return InvalidTransaction::Future.into()
+			}
 
-		Ok(())
+			// One price of a pair per authority and block.
+			ValidTransaction::with_tag_prefix("OffchainWorkerUnsignedTx")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((who, pair, price_payload.block_number))
+				.longevity(T::UnsignedLongevity::get())
+				.propagate(true)
+				.build()
+		}
 	}
+}
 
-	fn fetch_price() -> Result<u32, http::Error> {
-		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(2000));
-		// Initiate an external HTTP GET request.
-		// This is using high-level wrappers from `sp_runtime`, for the low-level calls that
-		// you can find in `sp_io`. The API is trying to be similar to `reqwest`, but
-		// since we are running in a custom WASM execution environment we can't simply
-		// import the library here.
-		let request = http::Request::get(
-			"https://min-api.cryptocompare.com/data/price?fsym=BTC&tsyms=USD"
-		);
-		// We set the deadline for sending of the request, note that awaiting response can
-		// have a separate deadline. Next we send the request, before that it's also possible
-		// to alter request headers or stream body content in case of non-GET requests.
-		let pending = request
-			.deadline(deadline)
-			.send()
-			.map_err(|_| http::Error::IoError)?;
-		// The request is already being processed by the host, we are free to do anything
-		// else in the worker (we can send multiple concurrent requests too).
-		// At some point however we probably want to check the response though,
-		// so we can block current thread and wait for it to finish.
-		// Note that since the request is being driven by the host, we don't have to wait
-		// for the request to have it complete, we will just not read the response.
-		let response = pending.try_wait(deadline)
-			.map_err(|_| http::Error::DeadlineReached)??;
-
-		if response.code != 200 {
-			debug::warn!("Unexpected status code: {}", response.code);
-			return Err(http::Error::Unknown);
+impl<T> From<PairError> for Error<T> {
+	fn from(error: PairError) -> Self {
+		match error {
+			PairError::InvalidSymbol => Error::InvalidSymbol,
+			PairError::AlreadyRegistered => Error::PairAlreadyRegistered,
+			PairError::NotRegistered => Error::PairNotRegistered,
+			PairError::TooMany => Error::TooManyPairs,
 		}
+	}
+}
 
-		let body = response.body().collect::<Vec<u8>>();
+impl<T: Config> Pallet<T> {
+	/// The aggregates of the prices of a pair over the last `window` blocks.
+	pub fn aggregates(pair: &(AssetId, QuoteId), window: BlockNumberFor<T>) -> PriceAggregates {
+		let now = frame_system::Pallet::<T>::block_number();
+		aggregation::aggregates(&Self::prices(pair), now, window)
+	}
 
-		let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
-			debug::warn!("Not UTF8 body");
-			http::Error::Unknown
-		})?;
+	/// The pair to fetch next, going round the registered pairs one per attempt of the offchain
+	/// worker, so a pair whose fetches fail does not hold up the others.
+	fn next_pair() -> Option<(AssetId, QuoteId)> {
+		let pairs = Self::pairs();
+		if pairs.is_empty() {
+			return None
+		}
+		let cursor = StorageValueRef::persistent(NEXT_PAIR_KEY);
+		let index = cursor.get::<u32>().ok().flatten().unwrap_or_default();
+		cursor.set(&index.wrapping_add(1));
+		pairs.get(index as usize % pairs.len()).cloned()
+	}
 
-		let price_info: PriceInfo = serde_json::from_str(&body_str).unwrap();
+	fn add_price(who: T::AccountId, pair: (AssetId, QuoteId), price: FixedU128) {
+		log::info!(target: LOG_TARGET, "Adding to the prices: {:?}", price);
+		let now = frame_system::Pallet::<T>::block_number();
+		Prices::<T>::mutate(&pair, |prices| {
+			// Once the buffer is full, the oldest price makes room for the new one.
+			let _ = prices.force_insert_keep_right(prices.len(), (now, price));
+		});
 
-		debug::warn!("Got price: {} cents", price_info.usd);
+		let (asset, quote) = pair;
+		Self::deposit_event(Event::NewPrice { asset, quote, price, who });
+	}
 
-		Ok(price_info.usd)
+	/// The keys in the keystore of the accounts that are authorities.
+	fn authority_keys() -> Vec<T::Public> {
+		ocw_common::local_keys::<T, T::AuthorityId>(&Self::authorities())
 	}
 
-}
+	fn fetch_price_and_send_unsigned(block_number: BlockNumberFor<T>) -> Result<(), SendError> {
+		if Self::next_unsigned_at() > block_number {
+			return Err(SendError::Skipped("Too early to send unsigned transaction"))
+		}
+
+		// Only the keys of authorities sign, the payloads of other keys would be rejected.
+		let signer =
+			Signer::<T, T::AuthorityId>::all_accounts().with_filter(Self::authority_keys());
+		if !signer.can_sign() {
+			return Err(SendError::Failed(
+				"No authority account available. Consider adding one via `author_insertKey` RPC.",
+			));
+		}
 
-impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
-	type Call = Call<T>;
-
-	fn validate_unsigned(
-		_source: TransactionSource,
-		call: &Self::Call
-	) -> TransactionValidity {
-		match call {
-			Call::submit_price_unsigned(input) =>
-				ValidTransaction::with_tag_prefix("OffchainWorkerUnsignedTx")
-					.and_provides(input)
-					.build(),
-			_ => InvalidTransaction::Call.into()
+		let (asset, quote) = Self::next_pair().ok_or(SendError::Skipped("No pair registered"))?;
+		let price = WORKER.fetch_price_with_backoff(block_number, &asset, &quote)?;
+
+		// Every authority key in the keystore signs its own payload, submitted in its own
+		// unsigned transaction.
+		let results = signer.send_unsigned_transaction(
+			|account| PricePayload {
+				public: account.public.clone(),
+				asset: asset.clone(),
+				quote: quote.clone(),
+				price,
+				block_number,
+			},
+			|price_payload, signature| Call::submit_price_unsigned_with_signed_payload {
+				price_payload,
+				signature,
+			},
+		);
+
+		for (acc, res) in &results {
+			match res {
+				Ok(()) =>
+					log::info!(target: LOG_TARGET, "[{:?}] Submitted price {:?}", acc.id, price),
+				Err(e) => log::error!(
+					target: LOG_TARGET,
+					"[{:?}] Failed to submit transaction: {:?}",
+					acc.id,
+					e
+				),
+			}
 		}
+
+		if results.iter().all(|(_, res)| res.is_err()) {
+			return Err(SendError::Failed("No transaction submitted"))
+		}
+
+		Ok(())
 	}
-}
\ No newline at end of file
+}
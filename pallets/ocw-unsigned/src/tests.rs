@@ -1,5 +1,537 @@
 // Tests to be written here
 
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use super::*;
+use crate::{mock::*, Error};
+use codec::Decode;
+use frame_support::{assert_noop, assert_ok, pallet_prelude::ValidateUnsigned, traits::Hooks};
+use ocw_common::{
+	testing::{expect_request, fetch_failures, price, symbol, BTC_USD, ETH_EUR},
+	FetchFailures,
+};
+use sp_core::{
+	offchain::HttpRequestStatus,
+	sr25519::{Pair as KeyPair, Signature},
+	Pair as _,
+};
+use sp_runtime::{
+	offchain::storage::StorageValueRef,
+	traits::{BadOrigin, Dispatchable},
+	DispatchResult,
+};
 
+fn register(asset: &str, quote: &str) {
+	assert_ok!(OcwUnsignedModule::register_pair(
+		RuntimeOrigin::root(),
+		symbol(asset),
+		symbol(quote)
+	));
+}
+
+fn key(seed: u8) -> KeyPair {
+	KeyPair::from_seed(&[seed; 32])
+}
+
+fn add_authority(key: &KeyPair) {
+	assert_ok!(OcwUnsignedModule::add_authority(RuntimeOrigin::root(), key.public()));
+}
+
+// Make the account of the key in the keystore an authority.
+fn add_local_authority() {
+	let public = sp_io::crypto::sr25519_public_keys(KEY_TYPE)[0];
+	assert_ok!(OcwUnsignedModule::add_authority(RuntimeOrigin::root(), public));
+}
+
+// A payload with the price of `asset` in USD at the current block, signed by `key`.
+fn signed_payload(
+	key: &KeyPair,
+	asset: &str,
+	price: FixedU128,
+) -> (PricePayloadOf<Test>, Signature) {
+	let payload = PricePayload {
+		public: key.public(),
+		asset: symbol(asset),
+		quote: symbol("USD"),
+		price,
+		block_number: System::block_number(),
+	};
+	let signature = key.sign(&payload.encode());
+	(payload, signature)
+}
+
+fn submit(key: &KeyPair, asset: &str, price: FixedU128) -> DispatchResult {
+	let (price_payload, signature) = signed_payload(key, asset, price);
+	OcwUnsignedModule::submit_price_unsigned_with_signed_payload(
+		RuntimeOrigin::none(),
+		price_payload,
+		signature,
+	)
+}
+
+#[test]
+fn submit_price_unsigned_with_signed_payload_works() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		let (price_payload, signature) = signed_payload(&key(1), "BTC", price(4200));
+		assert_noop!(
+			OcwUnsignedModule::submit_price_unsigned_with_signed_payload(
+				RuntimeOrigin::signed(key(1).public()),
+				price_payload,
+				signature
+			),
+			BadOrigin
+		);
+		assert_noop!(submit(&key(1), "ETH", price(4200)), Error::<Test>::PairNotRegistered);
+
+		assert_ok!(submit(&key(1), "BTC", price(4200)));
+		assert_eq!(
+			OcwUnsignedModule::prices((symbol("BTC"), symbol("USD"))),
+			vec![(1, price(4200))]
+		);
+		System::assert_last_event(
+			Event::<Test>::NewPrice {
+				asset: symbol("BTC"),
+				quote: symbol("USD"),
+				price: price(4200),
+				who: key(1).public(),
+			}
+			.into(),
+		);
+	});
+}
+
+// Include the unsigned transaction `tx` from the pool in the block, as the next block's worker
+// sees it, and return its payload.
+fn include(tx: Vec<u8>) -> PricePayloadOf<Test> {
+	let tx = Extrinsic::decode(&mut &*tx).unwrap();
+	let RuntimeCall::OcwUnsignedModule(call) = tx.call else { panic!("unexpected call") };
+	let Call::submit_price_unsigned_with_signed_payload { price_payload, .. } = &call else {
+		panic!("unexpected call")
+	};
+	let price_payload = price_payload.clone();
+	assert_ok!(OcwUnsignedModule::validate_unsigned(TransactionSource::External, &call));
+	assert_ok!(RuntimeCall::from(call).dispatch(RuntimeOrigin::none()));
+	price_payload
+}
+
+// Submit `price` of BTC in USD at block `at`.
+fn submit_at(at: u64, price: FixedU128) {
+	System::set_block_number(at);
+	assert_ok!(submit(&key(1), "BTC", price));
+}
+
+#[test]
+fn prices_are_a_ring_buffer() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		for at in 1..=4 {
+			submit_at(at, price(at.into()));
+		}
+		submit_at(5, price(5));
+
+		assert_eq!(
+			OcwUnsignedModule::prices((symbol("BTC"), symbol("USD"))),
+			vec![(2, price(2)), (3, price(3)), (4, price(4)), (5, price(5))]
+		);
+	});
+}
+
+#[test]
+fn aggregates_cover_block_window() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		let pair = (symbol("BTC"), symbol("USD"));
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 10), PriceAggregates::default());
+
+		submit_at(1, price(10));
+		submit_at(3, price(40));
+		submit_at(3, price(20));
+		submit_at(6, price(90));
+		System::set_block_number(8);
+
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 10).mean, Some(price(40)));
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 10).median, Some(price(30)));
+		// Only the prices of blocks 2 to 8.
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 7).mean, Some(price(50)));
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 7).median, Some(price(40)));
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 1).mean, None);
+
+		// 0.20 from block 3 to 6, then 0.90 until block 8.
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 5).twap, Some(price(48)));
+		// 0.90 since block 6.
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 2).twap, Some(price(90)));
+		assert_eq!(OcwUnsignedModule::aggregates(&pair, 0).twap, None);
+	});
+}
+
+#[test]
+fn register_and_deregister_pair_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OcwUnsignedModule::register_pair(
+				RuntimeOrigin::signed(key(1).public()),
+				symbol("BTC"),
+				symbol("USD")
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			OcwUnsignedModule::register_pair(RuntimeOrigin::root(), symbol("BTC"), symbol("usd ")),
+			Error::<Test>::InvalidSymbol
+		);
+
+		register("BTC", "USD");
+		assert_noop!(
+			OcwUnsignedModule::register_pair(RuntimeOrigin::root(), symbol("BTC"), symbol("USD")),
+			Error::<Test>::PairAlreadyRegistered
+		);
+		register("ETH", "USD");
+		register("DOT", "USD");
+		assert_noop!(
+			OcwUnsignedModule::register_pair(RuntimeOrigin::root(), symbol("KSM"), symbol("USD")),
+			Error::<Test>::TooManyPairs
+		);
+
+		assert_ok!(submit(&key(1), "ETH", price(4200)));
+		assert_ok!(OcwUnsignedModule::deregister_pair(
+			RuntimeOrigin::root(),
+			symbol("ETH"),
+			symbol("USD")
+		));
+		System::assert_last_event(
+			Event::<Test>::PairDeregistered { asset: symbol("ETH"), quote: symbol("USD") }.into(),
+		);
+		assert_eq!(
+			OcwUnsignedModule::pairs().into_inner(),
+			vec![(symbol("BTC"), symbol("USD")), (symbol("DOT"), symbol("USD"))]
+		);
+		assert!(OcwUnsignedModule::prices((symbol("ETH"), symbol("USD"))).is_empty());
+		assert_noop!(
+			OcwUnsignedModule::deregister_pair(RuntimeOrigin::root(), symbol("ETH"), symbol("USD")),
+			Error::<Test>::PairNotRegistered
+		);
+	});
+}
+
+#[test]
+fn add_and_remove_authority_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OcwUnsignedModule::add_authority(
+				RuntimeOrigin::signed(key(1).public()),
+				key(1).public()
+			),
+			BadOrigin
+		);
+
+		add_authority(&key(1));
+		System::assert_last_event(Event::<Test>::AuthorityAdded { who: key(1).public() }.into());
+		assert_noop!(
+			OcwUnsignedModule::add_authority(RuntimeOrigin::root(), key(1).public()),
+			Error::<Test>::AlreadyAuthority
+		);
+		add_authority(&key(2));
+		assert_noop!(
+			OcwUnsignedModule::add_authority(RuntimeOrigin::root(), key(3).public()),
+			Error::<Test>::TooManyAuthorities
+		);
+
+		assert_noop!(
+			OcwUnsignedModule::remove_authority(
+				RuntimeOrigin::signed(key(1).public()),
+				key(1).public()
+			),
+			BadOrigin
+		);
+		assert_ok!(OcwUnsignedModule::remove_authority(RuntimeOrigin::root(), key(1).public()));
+		System::assert_last_event(Event::<Test>::AuthorityRemoved { who: key(1).public() }.into());
+		assert_eq!(OcwUnsignedModule::authorities().into_inner(), vec![key(2).public()]);
+		assert_noop!(
+			OcwUnsignedModule::remove_authority(RuntimeOrigin::root(), key(1).public()),
+			Error::<Test>::NotAuthority
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_checks_signed_payload() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		add_authority(&key(1));
+		let validate = |(price_payload, signature): (PricePayloadOf<Test>, Signature)| {
+			OcwUnsignedModule::validate_unsigned(
+				TransactionSource::External,
+				&Call::submit_price_unsigned_with_signed_payload { price_payload, signature },
+			)
+		};
+
+		assert!(validate(signed_payload(&key(1), "BTC", price(4200))).is_ok());
+
+		// The payload was changed after it was signed.
+		let (mut payload, signature) = signed_payload(&key(1), "BTC", price(4200));
+		payload.price = price(1);
+		assert_eq!(validate((payload, signature)), InvalidTransaction::BadProof.into());
+
+		// Signed by a key which is not an authority.
+		assert_eq!(
+			validate(signed_payload(&key(2), "BTC", price(4200))),
+			InvalidTransaction::BadSigner.into()
+		);
+		assert_eq!(
+			validate(signed_payload(&key(1), "ETH", price(4200))),
+			InvalidTransaction::Call.into()
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_waits_for_unsigned_interval() {
+	new_test_ext().execute_with(|| {
+		register("BTC", "USD");
+		add_authority(&key(1));
+		let validate = |(price_payload, signature): (PricePayloadOf<Test>, Signature)| {
+			OcwUnsignedModule::validate_unsigned(
+				TransactionSource::External,
+				&Call::submit_price_unsigned_with_signed_payload { price_payload, signature },
+			)
+		};
+
+		let valid = validate(signed_payload(&key(1), "BTC", price(4200))).unwrap();
+		assert_eq!((valid.priority, valid.longevity, valid.propagate), (100, 5, true));
+
+		assert_ok!(submit(&key(1), "BTC", price(4200)));
+		assert_eq!(OcwUnsignedModule::next_unsigned_at(), 3);
+		System::set_block_number(2);
+		assert_eq!(
+			validate(signed_payload(&key(1), "BTC", price(4200))),
+			InvalidTransaction::Stale.into()
+		);
+
+		System::set_block_number(3);
+		assert!(validate(signed_payload(&key(1), "BTC", price(4200))).is_ok());
+		// Fetched in a block not imported yet.
+		let (mut payload, _) = signed_payload(&key(1), "BTC", price(4200));
+		payload.block_number = 4;
+		let signature = key(1).sign(&payload.encode());
+		assert_eq!(validate((payload, signature)), InvalidTransaction::Future.into());
+	});
+}
+
+#[test]
+fn offchain_worker_fetches_pairs_in_round_robin() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		register("ETH", "EUR");
+		add_local_authority();
+		let public = sp_io::crypto::sr25519_public_keys(KEY_TYPE)[0];
+
+		// A price every `UnsignedInterval` of 2 blocks, so only odd blocks with 2 pairs.
+		for (block, asset, quote, uri, body, hundredths) in [
+			(1, "BTC", "USD", BTC_USD, &br#"{"USD": 155.23}"#[..], 15523),
+			(3, "ETH", "EUR", ETH_EUR, &br#"{"EUR": 3012.5}"#[..], 301250),
+			(5, "BTC", "USD", BTC_USD, &br#"{"USD": 156}"#[..], 15600),
+		] {
+			System::set_block_number(block);
+			expect_request(&mut offchain_state.write(), uri, body);
+			OcwUnsignedModule::offchain_worker(block);
+
+			let tx = pool_state.write().transactions.pop().unwrap();
+			assert!(pool_state.read().transactions.is_empty());
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			assert_eq!(tx.signature, None);
+			let RuntimeCall::OcwUnsignedModule(Call::submit_price_unsigned_with_signed_payload {
+				price_payload,
+				signature,
+			}) = tx.call
+			else {
+				panic!("unexpected call")
+			};
+			assert_eq!(
+				price_payload,
+				PricePayload {
+					public,
+					asset: symbol(asset),
+					quote: symbol(quote),
+					price: price(hundredths),
+					block_number: block,
+				}
+			);
+			assert!(SignedPayload::<Test>::verify::<sr25519::AuthId>(
+				&price_payload,
+				signature.clone()
+			));
+
+			// Include the transaction in the block, as the next block's worker sees it.
+			let call = Call::submit_price_unsigned_with_signed_payload { price_payload, signature };
+			assert_ok!(OcwUnsignedModule::validate_unsigned(TransactionSource::External, &call));
+			assert_ok!(RuntimeCall::from(call).dispatch(RuntimeOrigin::none()));
+			assert_eq!(OcwUnsignedModule::next_unsigned_at(), block + 2);
+		}
+		assert_eq!(OcwUnsignedModule::prices((symbol("ETH"), symbol("EUR"))).len(), 1);
+		assert_eq!(OcwUnsignedModule::prices((symbol("BTC"), symbol("USD"))).len(), 2);
+	});
+}
+
+#[test]
+fn next_pair_moves_on_every_attempt() {
+	let (mut ext, ..) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		register("ETH", "USD");
+		register("DOT", "USD");
+		for asset in ["BTC", "ETH", "DOT", "BTC", "ETH"] {
+			assert_eq!(OcwUnsignedModule::next_pair(), Some((symbol(asset), symbol("USD"))));
+		}
+		assert_eq!(StorageValueRef::persistent(NEXT_PAIR_KEY).get::<u32>().unwrap(), Some(5));
+
+		// Deregistering a pair keeps the cursor among the registered pairs.
+		assert_ok!(OcwUnsignedModule::deregister_pair(
+			RuntimeOrigin::root(),
+			symbol("ETH"),
+			symbol("USD")
+		));
+		assert_eq!(OcwUnsignedModule::next_pair(), Some((symbol("DOT"), symbol("USD"))));
+		assert_eq!(OcwUnsignedModule::next_pair(), Some((symbol("BTC"), symbol("USD"))));
+	});
+}
+
+#[test]
+fn offchain_worker_goes_past_a_failing_pair() {
+	let (mut ext, offchain_state, pool_state, statuses) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		register("ETH", "EUR");
+		add_local_authority();
+
+		for block in 1..=11 {
+			System::set_block_number(block);
+			match block {
+				// BTC/USD always fails, and backs off on its turn at block 10.
+				1 | 4 | 7 => {
+					expect_request(&mut offchain_state.write(), BTC_USD, br#"{"USD": 155.23}"#);
+					statuses.write().push_back(HttpRequestStatus::Finished(500));
+				},
+				// ETH/EUR on the next attempt, once the `UnsignedInterval` of 2 blocks has passed.
+				2 | 5 | 8 | 11 =>
+					expect_request(&mut offchain_state.write(), ETH_EUR, br#"{"EUR": 3012.5}"#),
+				// Too early to send.
+				_ => (),
+			}
+			OcwUnsignedModule::offchain_worker(block);
+
+			if let Some(tx) = pool_state.write().transactions.pop() {
+				let price_payload = include(tx);
+				assert_eq!(
+					(price_payload.asset, price_payload.quote, price_payload.block_number),
+					(symbol("ETH"), symbol("EUR"), block)
+				);
+			}
+		}
+		assert!(pool_state.read().transactions.is_empty());
+		assert_eq!(OcwUnsignedModule::prices((symbol("ETH"), symbol("EUR"))).len(), 4);
+		assert!(OcwUnsignedModule::prices((symbol("BTC"), symbol("USD"))).is_empty());
+		assert_eq!(
+			fetch_failures(&WORKER, "BTC", "USD"),
+			FetchFailures { consecutive: 3, retry_at: 11, status: 3, ..Default::default() }
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_waits_for_next_unsigned_at() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		add_local_authority();
+		NextUnsignedAt::<Test>::put(3);
+
+		// Neither fetches nor submits before block 3.
+		OcwUnsignedModule::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+
+		expect_request(&mut offchain_state.write(), BTC_USD, br#"{"USD": 155.23}"#);
+		OcwUnsignedModule::offchain_worker(3);
+		assert_eq!(pool_state.read().transactions.len(), 1);
+	});
+}
+
+#[test]
+fn offchain_worker_signs_with_authority_keys_only() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		add_authority(&key(1));
+
+		// The key in the keystore is not an authority, so there is no request either.
+		OcwUnsignedModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+		assert_eq!(StorageValueRef::persistent(LAST_SEND_KEY).get::<u64>().unwrap(), None);
+
+		add_local_authority();
+		expect_request(&mut offchain_state.write(), BTC_USD, br#"{"USD": 155.23}"#);
+		OcwUnsignedModule::offchain_worker(2);
+		assert_eq!(pool_state.read().transactions.len(), 1);
+		assert_eq!(
+			OcwUnsignedModule::authority_keys(),
+			sp_io::crypto::sr25519_public_keys(KEY_TYPE)
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_without_pairs_does_nothing() {
+	let (mut ext, _, pool_state, _) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		OcwUnsignedModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+// Run the offchain worker of a local authority at block 1, with the request of the price of BTC
+// in USD answered by `body` and `status`, if any, and return the `FetchFailures` of the pair once
+// it is checked that nothing was submitted.
+fn failed_fetch(status: Option<HttpRequestStatus>, body: &[u8]) -> FetchFailures<u64> {
+	let (mut ext, offchain_state, pool_state, statuses) = new_offchain_ext();
+
+	ext.execute_with(|| {
+		register("BTC", "USD");
+		add_local_authority();
+		expect_request(&mut offchain_state.write(), BTC_USD, body);
+		statuses.write().extend(status);
+		OcwUnsignedModule::offchain_worker(1);
+
+		assert!(pool_state.read().transactions.is_empty());
+		assert_eq!(StorageValueRef::persistent(LAST_SEND_KEY).get::<u64>().unwrap(), None);
+		fetch_failures(&WORKER, "BTC", "USD")
+	})
+}
+
+#[test]
+fn offchain_worker_backs_off_after_error_status() {
+	assert_eq!(
+		failed_fetch(Some(HttpRequestStatus::Finished(500)), br#"{"USD": 155.23}"#),
+		FetchFailures { consecutive: 1, retry_at: 2, status: 1, ..Default::default() }
+	);
+}
+
+#[test]
+fn offchain_worker_backs_off_after_malformed_json() {
+	assert_eq!(
+		failed_fetch(None, br#"{"USD": 155.23"#),
+		FetchFailures { consecutive: 1, retry_at: 2, json: 1, ..Default::default() }
+	);
+}
+
+#[test]
+fn offchain_worker_backs_off_after_timeout() {
+	assert_eq!(
+		failed_fetch(Some(HttpRequestStatus::DeadlineReached), br#"{"USD": 155.23}"#),
+		FetchFailures { consecutive: 1, retry_at: 2, http: 1, ..Default::default() }
+	);
+}
@@ -0,0 +1,111 @@
+//! Weights for pallet_ocw_unsigned
+//!
+//! These are estimates counted from the storage accesses of each call, not benchmark results.
+//! Regenerate this file with `benchmark pallet --pallet pallet_ocw_unsigned` before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_ocw_unsigned.
+pub trait WeightInfo {
+	fn register_pair() -> Weight;
+	fn deregister_pair() -> Weight;
+	fn submit_price_unsigned_with_signed_payload() -> Weight;
+	fn add_authority() -> Weight;
+	fn remove_authority() -> Weight;
+}
+
+/// Weights for pallet_ocw_unsigned using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `OcwUnsignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	fn register_pair() -> Weight {
+		Weight::from_parts(13_000_000, 2030)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwUnsignedModule::Prices` (r:0 w:1)
+	/// Proof: `OcwUnsignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	fn deregister_pair() -> Weight {
+		Weight::from_parts(16_000_000, 2030)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Pairs` (r:1 w:0)
+	/// Proof: `OcwUnsignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwUnsignedModule::Prices` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	/// Storage: `OcwUnsignedModule::NextUnsignedAt` (r:0 w:1)
+	/// Proof: `OcwUnsignedModule::NextUnsignedAt` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn submit_price_unsigned_with_signed_payload() -> Weight {
+		Weight::from_parts(22_000_000, 4797)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Authorities` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Authorities` (`max_values`: Some(1), `max_size`: Some(513), added: 1008, mode: `MaxEncodedLen`)
+	fn add_authority() -> Weight {
+		Weight::from_parts(12_000_000, 1998)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Authorities` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Authorities` (`max_values`: Some(1), `max_size`: Some(513), added: 1008, mode: `MaxEncodedLen`)
+	fn remove_authority() -> Weight {
+		Weight::from_parts(12_000_000, 1998)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: `OcwUnsignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	fn register_pair() -> Weight {
+		Weight::from_parts(13_000_000, 2030)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Pairs` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwUnsignedModule::Prices` (r:0 w:1)
+	/// Proof: `OcwUnsignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	fn deregister_pair() -> Weight {
+		Weight::from_parts(16_000_000, 2030)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Pairs` (r:1 w:0)
+	/// Proof: `OcwUnsignedModule::Pairs` (`max_values`: Some(1), `max_size`: Some(545), added: 1040, mode: `MaxEncodedLen`)
+	/// Storage: `OcwUnsignedModule::Prices` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Prices` (`max_values`: None, `max_size`: Some(1332), added: 3807, mode: `MaxEncodedLen`)
+	/// Storage: `OcwUnsignedModule::NextUnsignedAt` (r:0 w:1)
+	/// Proof: `OcwUnsignedModule::NextUnsignedAt` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn submit_price_unsigned_with_signed_payload() -> Weight {
+		Weight::from_parts(22_000_000, 4797)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Authorities` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Authorities` (`max_values`: Some(1), `max_size`: Some(513), added: 1008, mode: `MaxEncodedLen`)
+	fn add_authority() -> Weight {
+		Weight::from_parts(12_000_000, 1998)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `OcwUnsignedModule::Authorities` (r:1 w:1)
+	/// Proof: `OcwUnsignedModule::Authorities` (`max_values`: Some(1), `max_size`: Some(513), added: 1008, mode: `MaxEncodedLen`)
+	fn remove_authority() -> Weight {
+		Weight::from_parts(12_000_000, 1998)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -0,0 +1,50 @@
+//! Runtime API definition of the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_std::vec::Vec;
+
+/// The details of a claim, as returned by [`PoeApi::claim`].
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ClaimDetails<AccountId, BlockNumber> {
+	/// The owner of the claim.
+	pub owner: AccountId,
+	/// The block at which the owner got the claim.
+	pub block_number: BlockNumber,
+	/// A description or URI of the document.
+	pub description: Option<Vec<u8>>,
+	/// The content type of the document.
+	pub content_type: Option<Vec<u8>>,
+	/// The block at which the claim is removed.
+	pub expires_at: Option<BlockNumber>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries of the claims notarized by the proof of existence pallet.
+	///
+	/// Claims are passed as raw bytes, a claim longer than the runtime accepts is never found.
+	pub trait PoeApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The owners of `claim` with the block at which each got it, oldest first.
+		///
+		/// Only the latest owners are kept on chain, up to the runtime's `MaxHistoryLength`.
+		fn claim_history(claim: Vec<u8>) -> Vec<(AccountId, BlockNumber)>;
+
+		/// The claims owned by `account`.
+		fn claims_of(account: AccountId) -> Vec<Vec<u8>>;
+
+		/// The details of `claim`, if it exists.
+		fn claim(claim: Vec<u8>) -> Option<ClaimDetails<AccountId, BlockNumber>>;
+
+		/// Whether `proof` shows that `leaf` is in the batch of the anchored Merkle `root`.
+		fn verify_inclusion(root: H256, leaf: Vec<u8>, proof: Vec<H256>) -> bool;
+	}
+}
@@ -7,33 +7,261 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+// Give `who` enough funds to hold the deposit of the longest claim many times over, full batches
+// included.
+fn fund_account<T: Config>(who: &T::AccountId) {
+	let deposit = Pallet::<T>::claim_deposit(max_claim_length::<T>());
+	let count = T::MaxBatchSize::get().saturating_add(100);
+	let balance =
+		T::Currency::minimum_balance().saturating_add(deposit.saturating_mul(count.into()));
+	T::Currency::set_balance(who, balance);
+}
+
+// The shortest claim the runtime accepts, the digest length if it only accepts digests.
+fn min_claim_length<T: Config>() -> u32 {
+	T::ClaimHasher::DIGEST_LENGTH.unwrap_or(0)
+}
+
+// The longest claim the runtime accepts, the digest length if it only accepts digests.
+fn max_claim_length<T: Config>() -> u32 {
+	T::ClaimHasher::DIGEST_LENGTH.unwrap_or_else(T::MaxClaimLength::get)
+}
+
+// A claim of `d` bytes.
+fn claim_of_length<T: Config>(d: u32) -> BoundedVec<u8, T::MaxClaimLength> {
+	BoundedVec::try_from(vec![0; d as usize]).unwrap()
+}
+
+// A claim of `d` bytes distinct for each `index`, as long as `d` is at least 4 bytes.
+fn unique_claim<T: Config>(index: u32, d: u32) -> BoundedVec<u8, T::MaxClaimLength> {
+	let mut claim = claim_of_length::<T>(d).into_inner();
+	claim.iter_mut().zip(index.to_le_bytes()).for_each(|(byte, i)| *byte = i);
+	BoundedVec::try_from(claim).unwrap()
+}
+
 benchmarks! {
 	create_claim {
-		let d in 0 .. T::MaxClaimLength::get();
-		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let d in min_claim_length::<T>() .. max_claim_length::<T>();
+		let claim = claim_of_length::<T>(d);
 		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
 	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
 	verify {
 		assert_last_event::<T>(Event::ClaimCreated(caller, claim).into())
 	}
 
 	revoke_claim {
-		let d in 0 .. T::MaxClaimLength::get();
-		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let d in min_claim_length::<T>() .. max_claim_length::<T>();
+		let claim = claim_of_length::<T>(d);
 		let caller: T::AccountId = whitelisted_caller();
-		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone()).is_ok());
-	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+		let owner: T::AccountId = account("owner", 0, 0);
+		fund_account::<T>(&owner);
+		// Worst case, an operator revokes a claim which frees its expiry slot.
+		let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		assert!(Pallet::<T>::create_claim_with_metadata(
+			RawOrigin::Signed(owner.clone()).into(),
+			claim.clone(),
+			None,
+			None,
+			Some(expires_at),
+		).is_ok());
+		assert!(Pallet::<T>::approve_operator(RawOrigin::Signed(owner.clone()).into(), caller.clone()).is_ok());
+	}: _(RawOrigin::Signed(caller), claim.clone())
 	verify {
-		assert_last_event::<T>(Event::ClaimRevoked(caller, claim).into())
+		assert_last_event::<T>(Event::ClaimRevoked(owner, claim).into())
 	}
 
 	transfer_claim {
-		let d in 0 .. T::MaxClaimLength::get();
-		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let d in min_claim_length::<T>() .. max_claim_length::<T>();
+		let claim = claim_of_length::<T>(d);
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let target: T::AccountId = account("target", 0, 0);
+		fund_account::<T>(&owner);
+		fund_account::<T>(&target);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone()).is_ok());
+		// Worst case, an operator transfers the claim and the oldest owner is dropped from a full
+		// history.
+		assert!(Pallet::<T>::approve_operator(RawOrigin::Signed(owner.clone()).into(), caller.clone()).is_ok());
+		let history = (0 .. T::MaxHistoryLength::get())
+			.map(|i| (account("owner", i, 0), frame_system::Pallet::<T>::block_number()))
+			.collect::<Vec<_>>();
+		ClaimHistory::<T>::insert(&claim, ClaimHistoryOf::<T>::try_from(history).unwrap());
+	}: _(RawOrigin::Signed(caller), claim.clone(), target.clone())
+	verify {
+		assert_last_event::<T>(Event::ClaimTransferred { from: owner, to: target, claim }.into())
+	}
+
+	create_claim_with_metadata {
+		let d in min_claim_length::<T>() .. max_claim_length::<T>();
+		let m in 0 .. T::MaxDescriptionLength::get();
+		let c in 0 .. T::MaxContentTypeLength::get();
+		let claim = claim_of_length::<T>(d);
+		let description = BoundedVec::try_from(vec![0; m as usize]).unwrap();
+		let content_type = BoundedVec::try_from(vec![0; c as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		// Worst case, the claim is the last one that can expire at its block.
+		let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		let expiries = (1 .. T::MaxExpiriesPerBlock::get())
+			.map(|i| unique_claim::<T>(i, max_claim_length::<T>()))
+			.collect::<Vec<_>>();
+		Expiries::<T>::insert(expires_at, BoundedVec::try_from(expiries).unwrap());
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		claim.clone(),
+		Some(description),
+		Some(content_type),
+		Some(expires_at)
+	)
+	verify {
+		assert_last_event::<T>(Event::ClaimCreated(caller, claim).into())
+	}
+
+	on_initialize {
+		let e in 0 .. T::MaxExpiriesPerBlock::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		for i in 0 .. e {
+			assert!(Pallet::<T>::create_claim_with_metadata(
+				RawOrigin::Signed(caller.clone()).into(),
+				unique_claim::<T>(i, max_claim_length::<T>()),
+				None,
+				None,
+				Some(expires_at),
+			).is_ok());
+		}
+	}: {
+		Pallet::<T>::on_initialize(expires_at);
+	}
+	verify {
+		assert_eq!(Proofs::<T>::iter().count(), 0);
+	}
+
+	offer_claim {
+		let d in min_claim_length::<T>() .. max_claim_length::<T>();
+		let claim = claim_of_length::<T>(d);
 		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
 		let target: T::AccountId = account("target", 0, 0);
+		fund_account::<T>(&owner);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone()).is_ok());
+		// Worst case, an operator offers the claim.
+		assert!(Pallet::<T>::approve_operator(RawOrigin::Signed(owner.clone()).into(), caller.clone()).is_ok());
+		let expires_at = Some(frame_system::Pallet::<T>::block_number() + 1u32.into());
+	}: _(RawOrigin::Signed(caller), claim.clone(), target.clone(), expires_at)
+	verify {
+		assert_last_event::<T>(Event::TransferOffered { from: owner, to: target, claim, expires_at }.into())
+	}
+
+	accept_claim {
+		let d in min_claim_length::<T>() .. max_claim_length::<T>();
+		let claim = claim_of_length::<T>(d);
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		fund_account::<T>(&caller);
+		fund_account::<T>(&owner);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone()).is_ok());
+		let expires_at = Some(frame_system::Pallet::<T>::block_number() + 1u32.into());
+		assert!(Pallet::<T>::offer_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone(), caller.clone(), expires_at).is_ok());
+		// Worst case, the oldest owner is dropped from a full history.
+		let history = (0 .. T::MaxHistoryLength::get())
+			.map(|i| (account("owner", i, 0), frame_system::Pallet::<T>::block_number()))
+			.collect::<Vec<_>>();
+		ClaimHistory::<T>::insert(&claim, ClaimHistoryOf::<T>::try_from(history).unwrap());
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+	verify {
+		assert_last_event::<T>(Event::ClaimTransferred { from: owner, to: caller, claim }.into())
+	}
+
+	cancel_offer {
+		let d in min_claim_length::<T>() .. max_claim_length::<T>();
+		let claim = claim_of_length::<T>(d);
+		let caller: T::AccountId = whitelisted_caller();
+		let target: T::AccountId = account("target", 0, 0);
+		fund_account::<T>(&caller);
 		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone()).is_ok());
-	}: _(RawOrigin::Signed(caller), claim, target)
+		assert!(Pallet::<T>::offer_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), target, None).is_ok());
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert_last_event::<T>(Event::OfferCancelled { claim }.into())
+	}
+
+	create_claims {
+		let n in 1 .. T::MaxBatchSize::get();
+		let d in min_claim_length::<T>().max(4) .. max_claim_length::<T>();
+		let claims = (0 .. n).map(|i| unique_claim::<T>(i, d)).collect::<Vec<_>>();
+		let last = claims.last().cloned().unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), claims)
+	verify {
+		assert_eq!(Proofs::<T>::iter().count() as u32, n);
+		assert_last_event::<T>(Event::ClaimCreated(caller, last).into())
+	}
+
+	revoke_claims {
+		let n in 1 .. T::MaxBatchSize::get();
+		let d in min_claim_length::<T>().max(4) .. max_claim_length::<T>();
+		let claims = (0 .. n).map(|i| unique_claim::<T>(i, d)).collect::<Vec<_>>();
+		let last = claims.last().cloned().unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		fund_account::<T>(&owner);
+		// Worst case, an operator revokes claims which each free their expiry slot.
+		for (i, claim) in (1u32 ..).zip(&claims) {
+			let expires_at = frame_system::Pallet::<T>::block_number() + i.into();
+			assert!(Pallet::<T>::create_claim_with_metadata(
+				RawOrigin::Signed(owner.clone()).into(),
+				claim.clone(),
+				None,
+				None,
+				Some(expires_at),
+			).is_ok());
+		}
+		assert!(Pallet::<T>::approve_operator(RawOrigin::Signed(owner.clone()).into(), caller.clone()).is_ok());
+	}: _(RawOrigin::Signed(caller), claims)
+	verify {
+		assert_eq!(Proofs::<T>::iter().count(), 0);
+		assert_last_event::<T>(Event::ClaimRevoked(owner, last).into())
+	}
+
+	approve_operator {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone())
+	verify {
+		assert_last_event::<T>(Event::OperatorApproved { owner: caller, operator }.into())
+	}
+
+	revoke_operator {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		assert!(Pallet::<T>::approve_operator(RawOrigin::Signed(caller.clone()).into(), operator.clone()).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone())
+	verify {
+		assert_last_event::<T>(Event::OperatorRevoked { owner: caller, operator }.into())
+	}
+
+	anchor_root {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let root = H256::repeat_byte(1);
+	}: _(RawOrigin::Signed(caller.clone()), root)
+	verify {
+		assert_last_event::<T>(Event::RootAnchored { owner: caller, root }.into())
+	}
+
+	remove_root {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let root = H256::repeat_byte(1);
+		assert!(Pallet::<T>::anchor_root(RawOrigin::Signed(caller.clone()).into(), root).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), root)
+	verify {
+		assert_last_event::<T>(Event::RootRemoved { owner: caller, root }.into())
+	}
 
 	impl_benchmark_test_suite!(PoeModule, crate::mock::new_test_ext(), crate::mock::Test);
 }
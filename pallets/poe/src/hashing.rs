@@ -0,0 +1,60 @@
+//! Hashers turning a document into the claim notarized on chain.
+//!
+//! With a digest hasher configured, only fixed-size digests are accepted as claims, so documents
+//! of any size can be notarized without their content being published. Clients compute the claim
+//! off chain with [`ClaimHasher::claim_of`], or [`Pallet::claim_of`](crate::Pallet::claim_of)
+//! when they know the runtime.
+
+use sp_core::hashing::{blake2_256, keccak_256, sha2_256};
+use sp_std::vec::Vec;
+
+/// Computes the claim of a document.
+pub trait ClaimHasher {
+	/// Length in bytes of the produced digests, `None` if documents are claimed as they are.
+	const DIGEST_LENGTH: Option<u32>;
+
+	/// The claim of `document`.
+	fn claim_of(document: &[u8]) -> Vec<u8>;
+}
+
+/// Documents are claimed as they are, without hashing.
+impl ClaimHasher for () {
+	const DIGEST_LENGTH: Option<u32> = None;
+
+	fn claim_of(document: &[u8]) -> Vec<u8> {
+		document.to_vec()
+	}
+}
+
+/// Claims are the Blake2-256 digest of the document.
+pub struct Blake2Hasher;
+
+impl ClaimHasher for Blake2Hasher {
+	const DIGEST_LENGTH: Option<u32> = Some(32);
+
+	fn claim_of(document: &[u8]) -> Vec<u8> {
+		blake2_256(document).to_vec()
+	}
+}
+
+/// Claims are the SHA2-256 digest of the document.
+pub struct Sha2Hasher;
+
+impl ClaimHasher for Sha2Hasher {
+	const DIGEST_LENGTH: Option<u32> = Some(32);
+
+	fn claim_of(document: &[u8]) -> Vec<u8> {
+		sha2_256(document).to_vec()
+	}
+}
+
+/// Claims are the Keccak-256 digest of the document.
+pub struct KeccakHasher;
+
+impl ClaimHasher for KeccakHasher {
+	const DIGEST_LENGTH: Option<u32> = Some(32);
+
+	fn claim_of(document: &[u8]) -> Vec<u8> {
+		keccak_256(document).to_vec()
+	}
+}
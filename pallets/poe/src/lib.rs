@@ -2,9 +2,20 @@
 
 /// A module for proof of existence
 
-use frame_support::pallet_prelude::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		fungible::{Inspect, Mutate, MutateHold},
+		tokens::{Fortitude, Precision, Restriction},
+	},
+	CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use frame_system::pallet_prelude::*;
+pub use hashing::ClaimHasher;
 pub use pallet::*;
+use sp_core::H256;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::vec::Vec;
 pub use weights::WeightInfo;
 
 #[cfg(test)]
@@ -14,40 +25,302 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod hashing;
+pub mod merkle;
+pub mod migrations;
 pub mod weights;
 
+const LOG_TARGET: &str = "runtime::poe";
+
+/// The balance type of the currency used for claim deposits.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The latest owners of a claim with the block at which each got it, oldest first.
+pub type ClaimHistoryOf<T> = BoundedVec<
+	(<T as frame_system::Config>::AccountId, BlockNumberFor<T>),
+	<T as Config>::MaxHistoryLength,
+>;
+
+/// The details of a claim.
+#[derive(
+	CloneNoBound,
+	Encode,
+	Decode,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct ClaimInfo<T: Config> {
+	/// The owner of the claim.
+	pub owner: T::AccountId,
+	/// The block at which the owner got the claim.
+	pub block_number: BlockNumberFor<T>,
+	/// A description or URI of the document.
+	pub description: Option<BoundedVec<u8, T::MaxDescriptionLength>>,
+	/// The MIME-like content type of the document, e.g. `application/pdf`.
+	pub content_type: Option<BoundedVec<u8, T::MaxContentTypeLength>>,
+	/// The block at which the claim is removed.
+	pub expires_at: Option<BlockNumberFor<T>>,
+}
+
+/// The details of an anchored Merkle root.
+#[derive(
+	CloneNoBound,
+	Encode,
+	Decode,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct RootInfo<T: Config> {
+	/// The account which anchored the root.
+	pub owner: T::AccountId,
+	/// The block at which the root was anchored.
+	pub block_number: BlockNumberFor<T>,
+	/// The deposit held from the owner until the root is removed.
+	pub deposit: BalanceOf<T>,
+}
+
+/// A pending offer of a claim to a new owner.
+#[derive(
+	CloneNoBound,
+	Encode,
+	Decode,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct TransferOffer<T: Config> {
+	/// The account the claim is offered to.
+	pub to: T::AccountId,
+	/// The block from which the offer can no longer be accepted.
+	pub expires_at: Option<BlockNumberFor<T>>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The maximum length of claim that can be added.
 		#[pallet::constant]
 		type MaxClaimLength: Get<u32>;
+		/// How documents are turned into claims.
+		///
+		/// Use `()` to claim documents as they are, or a digest hasher from [`hashing`] to only
+		/// accept fixed-size digests.
+		type ClaimHasher: ClaimHasher;
+		/// The maximum length of the description of a claim.
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+		/// The maximum length of the content type of a claim.
+		#[pallet::constant]
+		type MaxContentTypeLength: Get<u32>;
+		/// The maximum number of claims that can expire at the same block.
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
+		/// The maximum number of owners kept in the history of a claim.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+		/// The maximum number of claims created or revoked in a single batch.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
 		/// The runtime event
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The currency in which claim deposits are held.
+		type Currency: Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+		/// The base deposit held for every claim.
+		#[pallet::constant]
+		type ClaimDepositBase: Get<BalanceOf<Self>>;
+		/// The additional deposit held for each byte of a claim and its metadata.
+		#[pallet::constant]
+		type ClaimDepositPerByte: Get<BalanceOf<Self>>;
 		/// Weight information for extrinsics in this pallet
 		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The funds are held as the storage deposit of a claim.
+		ClaimDeposit,
+		/// The funds are held as the storage deposit of an anchored Merkle root.
+		RootDeposit,
+	}
+
 	#[pallet::storage]
 	#[pallet::getter(fn proofs)]
-	pub type Proofs<T: Config> = StorageMap<
+	pub type Proofs<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, ClaimInfo<T>>;
+
+	/// The claims of each owner, to list them without iterating over `Proofs`.
+	#[pallet::storage]
+	pub type OwnedClaims<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxClaimLength>,
+		(),
+	>;
+
+	/// The operators approved by an owner to revoke and transfer its claims on its behalf.
+	#[pallet::storage]
+	pub type Operators<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// The anchored Merkle roots, each standing for a batch of documents.
+	///
+	/// See [`merkle`] for how the roots are built.
+	#[pallet::storage]
+	pub type Roots<T: Config> = StorageMap<_, Blake2_128Concat, H256, RootInfo<T>>;
+
+	/// The deposit held from the owner of a claim.
+	///
+	/// Claims created at genesis have no deposit.
+	#[pallet::storage]
+	#[pallet::getter(fn deposits)]
+	pub type Deposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, BalanceOf<T>>;
+
+	/// The claims expiring at a block.
+	///
+	/// Claims revoked before their expiry are skipped when the block is reached.
+	#[pallet::storage]
+	pub type Expiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<BoundedVec<u8, T::MaxClaimLength>, T::MaxExpiriesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The owners of a claim, the oldest are dropped once `MaxHistoryLength` is reached.
+	///
+	/// The history is removed with the claim.
+	#[pallet::storage]
+	pub type ClaimHistory<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
 		BoundedVec<u8, T::MaxClaimLength>,
-		(T::AccountId, BlockNumberFor<T>),
+		ClaimHistoryOf<T>,
+		ValueQuery,
 	>;
 
+	/// The offers of claims to new owners, waiting for their acceptance.
+	///
+	/// An offer is removed once accepted or cancelled, or when the claim is transferred or removed.
+	#[pallet::storage]
+	pub type PendingTransfers<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, TransferOffer<T>>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Claims created at genesis, as `(claim, owner)` pairs.
+		pub claims: Vec<(Vec<u8>, T::AccountId)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (claim, owner) in &self.claims {
+				let claim: BoundedVec<u8, T::MaxClaimLength> =
+					claim.clone().try_into().expect("genesis claim exceeds `MaxClaimLength`");
+				assert!(Pallet::<T>::is_valid_claim(&claim), "genesis claim is not a digest");
+				assert!(!Proofs::<T>::contains_key(&claim), "duplicate claim in genesis");
+
+				let block_number = BlockNumberFor::<T>::zero();
+				Pallet::<T>::record_owner(&claim, owner.clone(), block_number);
+				OwnedClaims::<T>::insert(owner, &claim, ());
+				Proofs::<T>::insert(
+					&claim,
+					ClaimInfo {
+						owner: owner.clone(),
+						block_number,
+						description: None,
+						content_type: None,
+						expires_at: None,
+					},
+				);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		ClaimCreated(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
 		ClaimRevoked(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// A claim reached its expiry block and was removed.
+		ClaimExpired(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// A claim deposit was held from the owner.
+		DepositHeld(T::AccountId, BalanceOf<T>),
+		/// A claim deposit was released back to the owner.
+		DepositReleased(T::AccountId, BalanceOf<T>),
+		/// A claim deposit was moved from the old owner to the new owner.
+		DepositMoved(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A claim was transferred to a new owner.
+		ClaimTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		},
+		/// A claim was offered to a new owner, who must accept it.
+		TransferOffered {
+			from: T::AccountId,
+			to: T::AccountId,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			expires_at: Option<BlockNumberFor<T>>,
+		},
+		/// A pending offer of a claim was cancelled.
+		OfferCancelled {
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		},
+		/// An owner approved an operator to manage its claims.
+		OperatorApproved {
+			owner: T::AccountId,
+			operator: T::AccountId,
+		},
+		/// An owner revoked the approval of an operator.
+		OperatorRevoked {
+			owner: T::AccountId,
+			operator: T::AccountId,
+		},
+		/// The Merkle root of a batch of documents was anchored.
+		RootAnchored {
+			owner: T::AccountId,
+			root: H256,
+		},
+		/// An anchored Merkle root was removed by its owner.
+		RootRemoved {
+			owner: T::AccountId,
+			root: H256,
+		},
 	}
 
 	#[pallet::error]
@@ -56,10 +329,57 @@ pub mod pallet {
 		ClaimTooLong,
 		ClaimNotExist,
 		NotClaimOwner,
+		/// The account cannot afford the deposit of the claim.
+		InsufficientBalance,
+		/// The claim is not a digest of the configured `ClaimHasher`.
+		InvalidDigest,
+		/// The expiry block of the claim is not in the future.
+		InvalidExpiry,
+		/// Too many claims already expire at the requested block.
+		TooManyExpiries,
+		/// There is no pending offer of the claim.
+		OfferNotExist,
+		/// The offer of the claim is not made to the sender.
+		NotOfferRecipient,
+		/// The offer of the claim has expired.
+		OfferExpired,
+		/// Neither the owner nor the recipient of the offer.
+		NotOfferParty,
+		/// The batch holds more than `MaxBatchSize` claims.
+		TooManyClaims,
+		/// The operator is already approved by the owner.
+		OperatorAlreadyApproved,
+		/// The account is not an operator of the owner.
+		NotOperator,
+		/// The Merkle root is already anchored.
+		RootAlreadyAnchored,
+		/// The Merkle root is not anchored.
+		RootNotExist,
+		/// The Merkle root was anchored by another account.
+		NotRootOwner,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let claims = Expiries::<T>::take(now);
+			let count = claims.len() as u32;
+
+			for claim in claims {
+				Self::expire_claim(claim, now);
+			}
+
+			T::WeightInfo::on_initialize(count)
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::ClaimHasher::DIGEST_LENGTH.map_or(true, |len| len <= T::MaxClaimLength::get()),
+				"`MaxClaimLength` is shorter than the digests of `ClaimHasher`"
+			);
+			assert!(T::MaxHistoryLength::get() > 0, "`MaxHistoryLength` must not be zero");
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -68,14 +388,7 @@ pub mod pallet {
 		pub fn create_claim(origin: OriginFor<T>, claim: BoundedVec<u8, T::MaxClaimLength>) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
-
-			Proofs::<T>::insert(
-				&claim,
-				(sender.clone(), frame_system::Pallet::<T>::block_number()),
-			);
-
-			Self::deposit_event(Event::ClaimCreated(sender, claim));
+			Self::do_create_claim(sender, claim, None, None, None)?;
 
 			Ok(().into())
 		}
@@ -85,16 +398,14 @@ pub mod pallet {
 		pub fn revoke_claim(origin: OriginFor<T>, claim: BoundedVec<u8, T::MaxClaimLength>) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
-
-			Proofs::<T>::remove(&claim);
-
-			Self::deposit_event(Event::ClaimRevoked(sender, claim));
+			Self::do_revoke_claim(sender, claim)?;
 
 			Ok(().into())
 		}
 
+		/// Move a claim and its deposit to `dest` right away, without its consent.
+		///
+		/// To have `dest` accept the claim first, offer it with `offer_claim` instead.
 		#[pallet::call_index(2)]
 		#[pallet::weight(T::WeightInfo::transfer_claim(claim.len() as u32))]
 		pub fn transfer_claim(
@@ -104,13 +415,458 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let (owner, _block_number) =
-				Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			Self::ensure_owner_or_operator(&sender, &info.owner)?;
+
+			Self::do_transfer_claim(claim, info, dest)?;
+
+			Ok(().into())
+		}
+
+		/// Create a claim with a description, a content type and an expiry block, all optional.
+		///
+		/// The metadata is charged the same per byte deposit as the claim. An expired claim is
+		/// removed at the beginning of its expiry block and its deposit released.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::create_claim_with_metadata(
+			claim.len() as u32,
+			description.as_ref().map_or(0, |description| description.len() as u32),
+			content_type.as_ref().map_or(0, |content_type| content_type.len() as u32),
+		))]
+		pub fn create_claim_with_metadata(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			description: Option<BoundedVec<u8, T::MaxDescriptionLength>>,
+			content_type: Option<BoundedVec<u8, T::MaxContentTypeLength>>,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::do_create_claim(sender, claim, description, content_type, expires_at)?;
+
+			Ok(().into())
+		}
+
+		/// Offer a claim the sender owns or operates to `dest`, who becomes its owner once
+		/// accepting it with `accept_claim`.
+		///
+		/// The offer cannot be accepted from `expires_at` on, if given. A new offer of the claim
+		/// replaces the pending one.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::offer_claim(claim.len() as u32))]
+		pub fn offer_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			dest: T::AccountId,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			Self::ensure_owner_or_operator(&sender, &info.owner)?;
+			if let Some(expires_at) = expires_at {
+				let now = frame_system::Pallet::<T>::block_number();
+				ensure!(expires_at > now, Error::<T>::InvalidExpiry);
+			}
+
+			PendingTransfers::<T>::insert(&claim, TransferOffer { to: dest.clone(), expires_at });
+
+			Self::deposit_event(Event::TransferOffered {
+				from: info.owner,
+				to: dest,
+				claim,
+				expires_at,
+			});
+
+			Ok(().into())
+		}
+
+		/// Accept the pending offer of a claim, taking over its ownership and deposit.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::accept_claim(claim.len() as u32))]
+		pub fn accept_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let offer = PendingTransfers::<T>::get(&claim).ok_or(Error::<T>::OfferNotExist)?;
+			ensure!(offer.to == sender, Error::<T>::NotOfferRecipient);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(offer.expires_at.map_or(true, |at| now < at), Error::<T>::OfferExpired);
+
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			Self::do_transfer_claim(claim, info, sender)?;
+
+			Ok(().into())
+		}
+
+		/// Cancel the pending offer of a claim.
+		///
+		/// Either the owner withdraws the offer or the recipient declines it, expired or not.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::cancel_offer(claim.len() as u32))]
+		pub fn cancel_offer(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let offer = PendingTransfers::<T>::get(&claim).ok_or(Error::<T>::OfferNotExist)?;
+			let is_owner = Proofs::<T>::get(&claim).map_or(false, |info| info.owner == sender);
+			ensure!(is_owner || offer.to == sender, Error::<T>::NotOfferParty);
+
+			PendingTransfers::<T>::remove(&claim);
 
-			Proofs::<T>::insert(&claim, (dest, frame_system::Pallet::<T>::block_number()));
+			Self::deposit_event(Event::OfferCancelled { claim });
 
 			Ok(().into())
 		}
+
+		/// Create up to `MaxBatchSize` claims at once.
+		///
+		/// Either all claims are created, each with its own `ClaimCreated` event, or none is.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::create_claims(
+			claims.len() as u32,
+			Pallet::<T>::longest_claim(claims),
+		))]
+		pub fn create_claims(
+			origin: OriginFor<T>,
+			claims: Vec<BoundedVec<u8, T::MaxClaimLength>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(claims.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::TooManyClaims);
+
+			for claim in claims {
+				Self::do_create_claim(sender.clone(), claim, None, None, None)?;
+			}
+
+			Ok(().into())
+		}
+
+		/// Revoke up to `MaxBatchSize` claims the sender owns or operates at once.
+		///
+		/// Either all claims are revoked, each with its own `ClaimRevoked` event, or none is.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::revoke_claims(
+			claims.len() as u32,
+			Pallet::<T>::longest_claim(claims),
+		))]
+		pub fn revoke_claims(
+			origin: OriginFor<T>,
+			claims: Vec<BoundedVec<u8, T::MaxClaimLength>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(claims.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::TooManyClaims);
+
+			for claim in claims {
+				Self::do_revoke_claim(sender.clone(), claim)?;
+			}
+
+			Ok(().into())
+		}
+
+		/// Approve `operator` to revoke and transfer the claims of the sender on its behalf.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::approve_operator())]
+		pub fn approve_operator(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			ensure!(
+				!Operators::<T>::contains_key(&owner, &operator),
+				Error::<T>::OperatorAlreadyApproved
+			);
+
+			Operators::<T>::insert(&owner, &operator, ());
+
+			Self::deposit_event(Event::OperatorApproved { owner, operator });
+
+			Ok(().into())
+		}
+
+		/// Revoke the approval of `operator` by the sender.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::revoke_operator())]
+		pub fn revoke_operator(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			ensure!(Operators::<T>::contains_key(&owner, &operator), Error::<T>::NotOperator);
+
+			Operators::<T>::remove(&owner, &operator);
+
+			Self::deposit_event(Event::OperatorRevoked { owner, operator });
+
+			Ok(().into())
+		}
+
+		/// Anchor the Merkle root of a batch of documents.
+		///
+		/// Documents of the batch are not stored, their inclusion is proven against the root
+		/// with `verify_inclusion`. `ClaimDepositBase` is held from the sender until the root is
+		/// removed with `remove_root`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::anchor_root())]
+		pub fn anchor_root(origin: OriginFor<T>, root: H256) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			ensure!(!Roots::<T>::contains_key(root), Error::<T>::RootAlreadyAnchored);
+
+			let deposit = T::ClaimDepositBase::get();
+			T::Currency::hold(&HoldReason::RootDeposit.into(), &owner, deposit)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+			Self::deposit_event(Event::DepositHeld(owner.clone(), deposit));
+
+			let block_number = frame_system::Pallet::<T>::block_number();
+			Roots::<T>::insert(root, RootInfo { owner: owner.clone(), block_number, deposit });
+
+			Self::deposit_event(Event::RootAnchored { owner, root });
+
+			Ok(().into())
+		}
+
+		/// Remove a Merkle root anchored by the sender and release its deposit.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::remove_root())]
+		pub fn remove_root(origin: OriginFor<T>, root: H256) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let info = Roots::<T>::get(root).ok_or(Error::<T>::RootNotExist)?;
+			ensure!(info.owner == sender, Error::<T>::NotRootOwner);
+
+			Roots::<T>::remove(root);
+			T::Currency::release(
+				&HoldReason::RootDeposit.into(),
+				&sender,
+				info.deposit,
+				Precision::BestEffort,
+			)?;
+			Self::deposit_event(Event::DepositReleased(sender.clone(), info.deposit));
+
+			Self::deposit_event(Event::RootRemoved { owner: sender, root });
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The claim of `document` under the configured `ClaimHasher`.
+	///
+	/// This can be used off chain to compute the claim of a document before submitting it.
+	pub fn claim_of(document: &[u8]) -> Result<BoundedVec<u8, T::MaxClaimLength>, Error<T>> {
+		T::ClaimHasher::claim_of(document).try_into().map_err(|_| Error::<T>::ClaimTooLong)
+	}
+
+	/// Whether `claim` has the length of the digests of the configured `ClaimHasher`.
+	pub fn is_valid_claim(claim: &[u8]) -> bool {
+		T::ClaimHasher::DIGEST_LENGTH.map_or(true, |len| claim.len() as u32 == len)
+	}
+
+	/// The deposit held for a claim of `length` bytes, metadata included.
+	pub fn claim_deposit(length: u32) -> BalanceOf<T> {
+		T::ClaimDepositBase::get()
+			.saturating_add(T::ClaimDepositPerByte::get().saturating_mul(length.into()))
+	}
+
+	/// The owners of `claim` with the block at which each got it, oldest first.
+	///
+	/// Empty if the claim does not exist or is longer than `MaxClaimLength`.
+	pub fn claim_history(claim: Vec<u8>) -> Vec<(T::AccountId, BlockNumberFor<T>)> {
+		BoundedVec::<u8, T::MaxClaimLength>::try_from(claim)
+			.map(|claim| ClaimHistory::<T>::get(claim).into_inner())
+			.unwrap_or_default()
+	}
+
+	/// The claims owned by `owner`.
+	pub fn claims_of(owner: &T::AccountId) -> Vec<BoundedVec<u8, T::MaxClaimLength>> {
+		OwnedClaims::<T>::iter_key_prefix(owner).collect()
+	}
+
+	/// The details of `claim`, `None` if it does not exist or is longer than `MaxClaimLength`.
+	pub fn claim_info(claim: Vec<u8>) -> Option<ClaimInfo<T>> {
+		BoundedVec::<u8, T::MaxClaimLength>::try_from(claim)
+			.ok()
+			.and_then(Proofs::<T>::get)
+	}
+
+	/// Whether `proof` shows that `leaf` is in the batch of the anchored `root`.
+	pub fn verify_inclusion(root: H256, leaf: &[u8], proof: &[H256]) -> bool {
+		Roots::<T>::contains_key(root) && merkle::verify_proof(&root, leaf, proof)
+	}
+
+	fn do_create_claim(
+		owner: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		description: Option<BoundedVec<u8, T::MaxDescriptionLength>>,
+		content_type: Option<BoundedVec<u8, T::MaxContentTypeLength>>,
+		expires_at: Option<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		ensure!(Self::is_valid_claim(&claim), Error::<T>::InvalidDigest);
+		ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		if let Some(expires_at) = expires_at {
+			ensure!(expires_at > now, Error::<T>::InvalidExpiry);
+			Expiries::<T>::try_append(expires_at, &claim)
+				.map_err(|_| Error::<T>::TooManyExpiries)?;
+		}
+
+		let length = claim.len() +
+			description.as_ref().map_or(0, |description| description.len()) +
+			content_type.as_ref().map_or(0, |content_type| content_type.len());
+		let deposit = Self::claim_deposit(length as u32);
+		T::Currency::hold(&HoldReason::ClaimDeposit.into(), &owner, deposit)
+			.map_err(|_| Error::<T>::InsufficientBalance)?;
+		Deposits::<T>::insert(&claim, deposit);
+		Self::deposit_event(Event::DepositHeld(owner.clone(), deposit));
+
+		Self::record_owner(&claim, owner.clone(), now);
+		OwnedClaims::<T>::insert(&owner, &claim, ());
+		Proofs::<T>::insert(
+			&claim,
+			ClaimInfo {
+				owner: owner.clone(),
+				block_number: now,
+				description,
+				content_type,
+				expires_at,
+			},
+		);
+
+		Self::deposit_event(Event::ClaimCreated(owner, claim));
+
+		Ok(())
+	}
+
+	/// Revoke `claim` on behalf of `who`, its owner or one of the owner's operators.
+	fn do_revoke_claim(
+		who: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+	) -> DispatchResult {
+		let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+		Self::ensure_owner_or_operator(&who, &info.owner)?;
+
+		if let Some(expires_at) = info.expires_at {
+			Self::cancel_expiry(&claim, expires_at);
+		}
+		Self::remove_claim(&claim, info.owner.clone())?;
+
+		Self::deposit_event(Event::ClaimRevoked(info.owner, claim));
+
+		Ok(())
+	}
+
+	/// Ensure `who` is `owner` or one of its approved operators.
+	fn ensure_owner_or_operator(who: &T::AccountId, owner: &T::AccountId) -> DispatchResult {
+		ensure!(
+			who == owner || Operators::<T>::contains_key(owner, who),
+			Error::<T>::NotClaimOwner
+		);
+		Ok(())
+	}
+
+	/// The length of the longest of `claims`.
+	fn longest_claim(claims: &[BoundedVec<u8, T::MaxClaimLength>]) -> u32 {
+		claims.iter().map(|claim| claim.len() as u32).max().unwrap_or_default()
+	}
+
+	/// Move `claim` and its deposit to `dest`, dropping any pending offer of it.
+	fn do_transfer_claim(
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		mut info: ClaimInfo<T>,
+		dest: T::AccountId,
+	) -> DispatchResult {
+		if let Some(deposit) = Deposits::<T>::get(&claim) {
+			T::Currency::transfer_on_hold(
+				&HoldReason::ClaimDeposit.into(),
+				&info.owner,
+				&dest,
+				deposit,
+				Precision::Exact,
+				Restriction::OnHold,
+				Fortitude::Polite,
+			)?;
+			Self::deposit_event(Event::DepositMoved(info.owner.clone(), dest.clone(), deposit));
+		}
+
+		let from = sp_std::mem::replace(&mut info.owner, dest.clone());
+		info.block_number = frame_system::Pallet::<T>::block_number();
+		Self::record_owner(&claim, dest.clone(), info.block_number);
+		OwnedClaims::<T>::remove(&from, &claim);
+		OwnedClaims::<T>::insert(&dest, &claim, ());
+		Proofs::<T>::insert(&claim, info);
+		PendingTransfers::<T>::remove(&claim);
+
+		Self::deposit_event(Event::ClaimTransferred { from, to: dest, claim });
+
+		Ok(())
+	}
+
+	/// Append `owner` to the history of `claim`, dropping the oldest owner if it is full.
+	fn record_owner(
+		claim: &BoundedVec<u8, T::MaxClaimLength>,
+		owner: T::AccountId,
+		block_number: BlockNumberFor<T>,
+	) {
+		ClaimHistory::<T>::mutate(claim, |history| {
+			if history.is_full() {
+				history.remove(0);
+			}
+			// Cannot fail, there is room left for at least one owner.
+			let _ = history.try_push((owner, block_number));
+		});
+	}
+
+	/// Remove `claim` and release its deposit to `owner`.
+	fn remove_claim(
+		claim: &BoundedVec<u8, T::MaxClaimLength>,
+		owner: T::AccountId,
+	) -> DispatchResult {
+		Proofs::<T>::remove(claim);
+		OwnedClaims::<T>::remove(&owner, claim);
+		ClaimHistory::<T>::remove(claim);
+		PendingTransfers::<T>::remove(claim);
+
+		if let Some(deposit) = Deposits::<T>::take(claim) {
+			T::Currency::release(
+				&HoldReason::ClaimDeposit.into(),
+				&owner,
+				deposit,
+				Precision::BestEffort,
+			)?;
+			Self::deposit_event(Event::DepositReleased(owner, deposit));
+		}
+
+		Ok(())
+	}
+
+	/// Free the slot of `claim` in the expiries of `expires_at`.
+	fn cancel_expiry(claim: &BoundedVec<u8, T::MaxClaimLength>, expires_at: BlockNumberFor<T>) {
+		Expiries::<T>::mutate_exists(expires_at, |claims| {
+			if let Some(list) = claims {
+				list.retain(|expiring| expiring != claim);
+				if list.is_empty() {
+					*claims = None;
+				}
+			}
+		});
+	}
+
+	/// Remove `claim` if it is still set to expire at `now`.
+	fn expire_claim(claim: BoundedVec<u8, T::MaxClaimLength>, now: BlockNumberFor<T>) {
+		let Some(info) = Proofs::<T>::get(&claim) else { return };
+		if info.expires_at != Some(now) {
+			return
+		}
+
+		if let Err(e) = Self::remove_claim(&claim, info.owner.clone()) {
+			log::error!(target: LOG_TARGET, "Failed to release deposit of expired claim: {:?}", e);
+		}
+
+		Self::deposit_event(Event::ClaimExpired(info.owner, claim));
 	}
 }
@@ -0,0 +1,79 @@
+//! Merkle trees anchoring a batch of documents with a single root.
+//!
+//! Leaves and inner nodes are hashed with Blake2-256 under distinct prefixes, and the two children
+//! of a node are sorted before being hashed together, so a proof is only the list of sibling
+//! hashes from the leaf up to the root. A node without sibling is moved up unchanged.
+//!
+//! Clients build the root of a batch with [`merkle_root`], anchor it with
+//! [`Pallet::anchor_root`](crate::Pallet::anchor_root) and later prove a single document was in
+//! the batch with [`merkle_proof`].
+
+use sp_core::{hashing::blake2_256, H256};
+use sp_std::vec::Vec;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// The hash of a leaf of the tree.
+pub fn hash_leaf(leaf: &[u8]) -> H256 {
+	let mut data = Vec::with_capacity(leaf.len() + 1);
+	data.push(LEAF_PREFIX);
+	data.extend_from_slice(leaf);
+	blake2_256(&data).into()
+}
+
+/// The hash of the parent of nodes `a` and `b`, in any order.
+fn hash_node(a: &H256, b: &H256) -> H256 {
+	let (left, right) = if a <= b { (a, b) } else { (b, a) };
+	let mut data = [0u8; 65];
+	data[0] = NODE_PREFIX;
+	data[1..33].copy_from_slice(left.as_bytes());
+	data[33..].copy_from_slice(right.as_bytes());
+	blake2_256(&data).into()
+}
+
+/// Whether `proof` shows that `leaf` is in the tree of `root`.
+pub fn verify_proof(root: &H256, leaf: &[u8], proof: &[H256]) -> bool {
+	proof.iter().fold(hash_leaf(leaf), |hash, sibling| hash_node(&hash, sibling)) == *root
+}
+
+/// The root of the tree of `leaves`, `None` if there are no leaves.
+#[cfg(feature = "std")]
+pub fn merkle_root<L: AsRef<[u8]>>(leaves: &[L]) -> Option<H256> {
+	let mut layer = leaves.iter().map(|leaf| hash_leaf(leaf.as_ref())).collect::<Vec<_>>();
+	while layer.len() > 1 {
+		layer = next_layer(&layer);
+	}
+	layer.pop()
+}
+
+/// The proof that the leaf at `index` is in the tree of `leaves`, `None` if out of bounds.
+#[cfg(feature = "std")]
+pub fn merkle_proof<L: AsRef<[u8]>>(leaves: &[L], mut index: usize) -> Option<Vec<H256>> {
+	let mut layer = leaves.iter().map(|leaf| hash_leaf(leaf.as_ref())).collect::<Vec<_>>();
+	if index >= layer.len() {
+		return None
+	}
+
+	let mut proof = Vec::new();
+	while layer.len() > 1 {
+		if let Some(sibling) = layer.get(index ^ 1) {
+			proof.push(*sibling);
+		}
+		layer = next_layer(&layer);
+		index /= 2;
+	}
+	Some(proof)
+}
+
+#[cfg(feature = "std")]
+fn next_layer(layer: &[H256]) -> Vec<H256> {
+	layer
+		.chunks(2)
+		.map(|pair| match pair {
+			[a, b] => hash_node(a, b),
+			[a] => *a,
+			_ => unreachable!("chunks are made of one or two nodes; qed"),
+		})
+		.collect()
+}
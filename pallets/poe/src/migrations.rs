@@ -0,0 +1,104 @@
+//! Storage migrations of the proof of existence pallet.
+
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+use sp_std::marker::PhantomData;
+
+/// Migration to storage version 1, storing [`ClaimInfo`] in `Proofs`.
+pub mod v1 {
+	use super::*;
+
+	/// The storage layout before version 1.
+	pub mod old {
+		use super::*;
+
+		/// `Proofs` storing `(owner, block_number)` tuples.
+		#[frame_support::storage_alias]
+		pub type Proofs<T: Config> = StorageMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			BoundedVec<u8, <T as Config>::MaxClaimLength>,
+			(<T as frame_system::Config>::AccountId, BlockNumberFor<T>),
+		>;
+	}
+
+	/// Turn every `(owner, block_number)` tuple of `Proofs` into a [`ClaimInfo`] without
+	/// metadata nor expiry.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				log::info!(target: LOG_TARGET, "Skipping migration to v1, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut count = 0u64;
+			Proofs::<T>::translate::<(T::AccountId, BlockNumberFor<T>), _>(
+				|_claim, (owner, block_number)| {
+					count.saturating_inc();
+					Some(ClaimInfo {
+						owner,
+						block_number,
+						description: None,
+						content_type: None,
+						expires_at: None,
+					})
+				},
+			);
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			log::info!(target: LOG_TARGET, "Migrated {} claims to v1", count);
+			T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((old::Proofs::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state")?;
+			ensure!(Proofs::<T>::iter().count() as u32 == count, "Claims lost in migration");
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version not updated");
+			Ok(())
+		}
+	}
+}
+
+/// Migration to storage version 2, indexing the claims of each owner in `OwnedClaims`.
+pub mod v2 {
+	use super::*;
+
+	/// Insert every claim of `Proofs` into `OwnedClaims`.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				log::info!(target: LOG_TARGET, "Skipping migration to v2, not at v1");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut count = 0u64;
+			for (claim, info) in Proofs::<T>::iter() {
+				OwnedClaims::<T>::insert(info.owner, claim, ());
+				count.saturating_inc();
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			log::info!(target: LOG_TARGET, "Indexed {} claims by owner in v2", count);
+			T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			for (claim, info) in Proofs::<T>::iter() {
+				ensure!(OwnedClaims::<T>::contains_key(info.owner, claim), "Claim not indexed");
+			}
+			ensure!(Pallet::<T>::on_chain_storage_version() == 2, "Storage version not updated");
+			Ok(())
+		}
+	}
+}
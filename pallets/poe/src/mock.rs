@@ -13,6 +13,7 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		PoeModule: pallet_poe,
 	}
 );
@@ -34,7 +35,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -43,13 +44,130 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = ();
+}
+
 impl pallet_poe::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxClaimLength = ConstU32<10>;
+	type ClaimHasher = ();
+	type MaxDescriptionLength = ConstU32<16>;
+	type MaxContentTypeLength = ConstU32<8>;
+	type MaxExpiriesPerBlock = ConstU32<2>;
+	type MaxHistoryLength = ConstU32<2>;
+	type MaxBatchSize = ConstU32<3>;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ClaimDepositBase = ConstU64<2>;
+	type ClaimDepositPerByte = ConstU64<1>;
 	type WeightInfo = ();
 }
 
+// Accounts endowed at genesis, `4` is left without funds.
+const ENDOWED_ACCOUNTS: [(u64, u64); 3] = [(1, 100), (2, 100), (3, 100)];
+
 // BUild genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+	new_test_ext_with_claims(vec![])
+}
+
+// Build genesis storage with the given `(claim, owner)` pairs pre-created.
+pub fn new_test_ext_with_claims(claims: Vec<(Vec<u8>, u64)>) -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = RuntimeGenesisConfig {
+		system: Default::default(),
+		balances: pallet_balances::GenesisConfig { balances: ENDOWED_ACCOUNTS.to_vec() },
+		poe_module: pallet_poe::GenesisConfig { claims },
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	// Events are not emitted on the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// A runtime whose claims must be Blake2-256 digests.
+pub mod digest {
+	use crate as pallet_poe;
+	use frame_support::{
+		derive_impl,
+		traits::{ConstU32, ConstU64},
+	};
+	use sp_runtime::BuildStorage;
+
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test
+		{
+			System: frame_system,
+			Balances: pallet_balances,
+			PoeModule: pallet_poe,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Test {
+		type Block = Block;
+		type AccountData = pallet_balances::AccountData<u64>;
+	}
+
+	impl pallet_balances::Config for Test {
+		type MaxLocks = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
+		type Balance = u64;
+		type RuntimeEvent = RuntimeEvent;
+		type DustRemoval = ();
+		type ExistentialDeposit = ConstU64<1>;
+		type AccountStore = System;
+		type WeightInfo = ();
+		type FreezeIdentifier = ();
+		type MaxFreezes = ();
+		type RuntimeHoldReason = RuntimeHoldReason;
+		type RuntimeFreezeReason = ();
+	}
+
+	impl pallet_poe::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type MaxClaimLength = ConstU32<40>;
+		type ClaimHasher = pallet_poe::hashing::Blake2Hasher;
+		type MaxDescriptionLength = ConstU32<16>;
+		type MaxContentTypeLength = ConstU32<8>;
+		type MaxExpiriesPerBlock = ConstU32<2>;
+		type MaxHistoryLength = ConstU32<2>;
+		type MaxBatchSize = ConstU32<3>;
+		type Currency = Balances;
+		type RuntimeHoldReason = RuntimeHoldReason;
+		type ClaimDepositBase = ConstU64<2>;
+		type ClaimDepositPerByte = ConstU64<1>;
+		type WeightInfo = ();
+	}
+
+	// Build genesis storage for the digest runtime.
+	pub fn new_test_ext() -> sp_io::TestExternalities {
+		let mut ext: sp_io::TestExternalities = RuntimeGenesisConfig {
+			system: Default::default(),
+			balances: pallet_balances::GenesisConfig { balances: super::ENDOWED_ACCOUNTS.to_vec() },
+			poe_module: Default::default(),
+		}
+		.build_storage()
+		.unwrap()
+		.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
 }
@@ -1,6 +1,17 @@
 use super::*;
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{Inspect, InspectHold},
+		GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+	},
+	BoundedVec,
+};
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::ClaimDeposit.into(), &who)
+}
 
 #[test]
 fn create_claim_works() {
@@ -9,7 +20,7 @@ fn create_claim_works() {
 		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone()));
 
 		assert_eq!(
-			Proofs::<Test>::get(&claim),
+			Proofs::<Test>::get(&claim).map(|info| (info.owner, info.block_number)),
 			Some((1, frame_system::Pallet::<Test>::block_number()))
 		);
 		assert_eq!(<<Test as Config>::MaxClaimLength as Get<u32>>::get(), 10);
@@ -75,7 +86,7 @@ fn transfer_claim_works() {
 		let bounded_claim =
 			BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(claim.clone()).unwrap();
 		assert_eq!(
-			Proofs::<Test>::get(&bounded_claim),
+			Proofs::<Test>::get(&bounded_claim).map(|info| (info.owner, info.block_number)),
 			Some((2, frame_system::Pallet::<Test>::block_number()))
 		);
 	})
@@ -105,3 +116,834 @@ fn transfer_claim_failed_with_wrong_owner() {
 		);
 	})
 }
+
+#[test]
+fn genesis_claims_are_created() {
+	new_test_ext_with_claims(vec![(vec![0, 1], 1), (vec![2], 2)]).execute_with(|| {
+		let claim =
+			BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(vec![0, 1]).unwrap();
+		assert_eq!(
+			Proofs::<Test>::get(&claim).map(|info| (info.owner, info.block_number)),
+			Some((1, 0))
+		);
+
+		let claim = BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(vec![2]).unwrap();
+		assert_eq!(
+			Proofs::<Test>::get(&claim).map(|info| (info.owner, info.block_number)),
+			Some((2, 0))
+		);
+	})
+}
+
+#[test]
+fn create_claim_holds_deposit() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone()));
+
+		// 2 base + 2 bytes * 1 per byte
+		assert_eq!(PoeModule::claim_deposit(2), 4);
+		assert_eq!(Deposits::<Test>::get(&claim), Some(4));
+		assert_eq!(held(1), 4);
+		assert_eq!(Balances::balance(&1), 96);
+		System::assert_has_event(Event::<Test>::DepositHeld(1, 4).into());
+	})
+}
+
+#[test]
+fn create_claim_failed_with_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+
+		assert_noop!(
+			PoeModule::create_claim(RuntimeOrigin::signed(4), claim.clone()),
+			Error::<Test>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn revoke_claim_releases_deposit() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()));
+
+		assert_eq!(Deposits::<Test>::get(&claim), None);
+		assert_eq!(held(1), 0);
+		assert_eq!(Balances::balance(&1), 100);
+		System::assert_has_event(Event::<Test>::DepositReleased(1, 4).into());
+	})
+}
+
+#[test]
+fn revoke_genesis_claim_without_deposit_works() {
+	new_test_ext_with_claims(vec![(vec![0, 1], 1)]).execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()));
+		assert_eq!(Balances::balance(&1), 100);
+	})
+}
+
+#[test]
+fn transfer_claim_moves_deposit() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim.clone(), 2));
+
+		assert_eq!(Deposits::<Test>::get(&claim), Some(4));
+		assert_eq!(held(1), 0);
+		assert_eq!(held(2), 4);
+		assert_eq!(Balances::total_balance(&1), 96);
+		assert_eq!(Balances::total_balance(&2), 104);
+		System::assert_has_event(Event::<Test>::DepositMoved(1, 2, 4).into());
+	})
+}
+
+#[test]
+fn claim_of_returns_document_without_hasher() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PoeModule::claim_of(&[0, 1]).unwrap().into_inner(), vec![0, 1]);
+		assert!(matches!(PoeModule::claim_of(&[0; 11]), Err(Error::<Test>::ClaimTooLong)));
+		assert!(PoeModule::is_valid_claim(&[0; 3]));
+	})
+}
+
+#[test]
+fn digest_hashers_work() {
+	use crate::hashing::{Blake2Hasher, KeccakHasher, Sha2Hasher};
+	use sp_core::hashing::{blake2_256, keccak_256, sha2_256};
+
+	let document = b"a document of any size".to_vec();
+
+	assert_eq!(Blake2Hasher::claim_of(&document), blake2_256(&document).to_vec());
+	assert_eq!(Sha2Hasher::claim_of(&document), sha2_256(&document).to_vec());
+	assert_eq!(KeccakHasher::claim_of(&document), keccak_256(&document).to_vec());
+	assert_eq!(<Blake2Hasher as ClaimHasher>::DIGEST_LENGTH, Some(32));
+	assert_eq!(<() as ClaimHasher>::claim_of(&document), document);
+}
+
+#[test]
+fn create_claim_failed_with_invalid_digest() {
+	use crate::mock::digest::{self, PoeModule, RuntimeOrigin};
+
+	digest::new_test_ext().execute_with(|| {
+		let short = BoundedVec::try_from(vec![0; 31]).unwrap();
+		let long = BoundedVec::try_from(vec![0; 33]).unwrap();
+		assert_noop!(
+			PoeModule::create_claim(RuntimeOrigin::signed(1), short),
+			Error::<digest::Test>::InvalidDigest
+		);
+		assert_noop!(
+			PoeModule::create_claim(RuntimeOrigin::signed(1), long),
+			Error::<digest::Test>::InvalidDigest
+		);
+
+		let claim = PoeModule::claim_of(b"a document of any size").unwrap();
+		assert_eq!(claim.len(), 32);
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone()));
+		assert!(Proofs::<digest::Test>::contains_key(&claim));
+	})
+}
+
+#[test]
+fn create_claim_with_metadata_works() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let description = BoundedVec::try_from(b"ipfs://doc".to_vec()).unwrap();
+		let content_type = BoundedVec::try_from(b"text/md".to_vec()).unwrap();
+
+		assert_ok!(PoeModule::create_claim_with_metadata(
+			RuntimeOrigin::signed(1),
+			claim.clone(),
+			Some(description.clone()),
+			Some(content_type.clone()),
+			Some(10),
+		));
+
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo {
+				owner: 1,
+				block_number: 1,
+				description: Some(description),
+				content_type: Some(content_type),
+				expires_at: Some(10),
+			})
+		);
+		assert_eq!(Expiries::<Test>::get(10).into_inner(), vec![claim.clone()]);
+		// 2 base + (2 claim + 10 description + 7 content type) bytes * 1 per byte
+		assert_eq!(Deposits::<Test>::get(&claim), Some(21));
+		assert_eq!(held(1), 21);
+	})
+}
+
+#[test]
+fn create_claim_with_metadata_failed_with_past_expiry() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+
+		assert_noop!(
+			PoeModule::create_claim_with_metadata(
+				RuntimeOrigin::signed(1),
+				claim.clone(),
+				None,
+				None,
+				Some(1)
+			),
+			Error::<Test>::InvalidExpiry
+		);
+	})
+}
+
+#[test]
+fn create_claim_with_metadata_failed_with_too_many_expiries() {
+	new_test_ext().execute_with(|| {
+		for byte in 0..2 {
+			let claim = BoundedVec::try_from(vec![byte]).unwrap();
+			assert_ok!(PoeModule::create_claim_with_metadata(
+				RuntimeOrigin::signed(1),
+				claim,
+				None,
+				None,
+				Some(10)
+			));
+		}
+
+		let claim = BoundedVec::try_from(vec![2]).unwrap();
+		assert_noop!(
+			PoeModule::create_claim_with_metadata(
+				RuntimeOrigin::signed(1),
+				claim,
+				None,
+				None,
+				Some(10)
+			),
+			Error::<Test>::TooManyExpiries
+		);
+	})
+}
+
+#[test]
+fn expired_claims_are_removed() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		assert_ok!(PoeModule::create_claim_with_metadata(
+			RuntimeOrigin::signed(1),
+			claim.clone(),
+			None,
+			None,
+			Some(10)
+		));
+
+		PoeModule::on_initialize(9);
+		assert!(Proofs::<Test>::contains_key(&claim));
+
+		PoeModule::on_initialize(10);
+		assert!(!Proofs::<Test>::contains_key(&claim));
+		assert_eq!(Deposits::<Test>::get(&claim), None);
+		assert_eq!(Expiries::<Test>::get(10).len(), 0);
+		assert_eq!(held(1), 0);
+		System::assert_last_event(Event::<Test>::ClaimExpired(1, claim).into());
+	})
+}
+
+#[test]
+fn revoke_claim_frees_expiry_slot() {
+	new_test_ext().execute_with(|| {
+		for byte in 0..2 {
+			let claim = BoundedVec::try_from(vec![byte]).unwrap();
+			assert_ok!(PoeModule::create_claim_with_metadata(
+				RuntimeOrigin::signed(1),
+				claim,
+				None,
+				None,
+				Some(10)
+			));
+		}
+		let revoked = BoundedVec::try_from(vec![0]).unwrap();
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), revoked.clone()));
+		assert!(!Expiries::<Test>::get(10).contains(&revoked));
+
+		let claim = BoundedVec::try_from(vec![2]).unwrap();
+		assert_ok!(PoeModule::create_claim_with_metadata(
+			RuntimeOrigin::signed(2),
+			claim.clone(),
+			None,
+			None,
+			Some(10)
+		));
+		assert_eq!(Expiries::<Test>::get(10).len(), 2);
+
+		assert_ok!(PoeModule::revoke_claims(
+			RuntimeOrigin::signed(1),
+			vec![BoundedVec::try_from(vec![1]).unwrap()]
+		));
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(2), claim));
+		assert!(!Expiries::<Test>::contains_key(10));
+	})
+}
+
+#[test]
+fn revoked_claims_are_skipped_at_expiry() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		assert_ok!(PoeModule::create_claim_with_metadata(
+			RuntimeOrigin::signed(1),
+			claim.clone(),
+			None,
+			None,
+			Some(10)
+		));
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()));
+		// The claim is created again without expiry.
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(2), claim.clone()));
+
+		PoeModule::on_initialize(10);
+		assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(2));
+	})
+}
+
+#[test]
+fn migrate_to_v1_works() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<PoeModule>();
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		crate::migrations::v1::old::Proofs::<Test>::insert(&claim, (1, 5));
+
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo {
+				owner: 1,
+				block_number: 5,
+				description: None,
+				content_type: None,
+				expires_at: None,
+			})
+		);
+		assert_eq!(PoeModule::on_chain_storage_version(), 1);
+	})
+}
+
+#[test]
+fn transfer_claim_emits_event() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim.clone(), 2));
+
+		System::assert_last_event(Event::<Test>::ClaimTransferred { from: 1, to: 2, claim }.into());
+	})
+}
+
+#[test]
+fn transfers_are_recorded_in_history() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		assert_eq!(PoeModule::claim_history(vec![0, 1]), vec![(1, 1)]);
+
+		System::set_block_number(2);
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim.clone(), 2));
+		assert_eq!(PoeModule::claim_history(vec![0, 1]), vec![(1, 1), (2, 2)]);
+
+		// Only the latest `MaxHistoryLength` owners are kept.
+		System::set_block_number(3);
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(2), claim.clone(), 3));
+		assert_eq!(PoeModule::claim_history(vec![0, 1]), vec![(2, 2), (3, 3)]);
+	})
+}
+
+#[test]
+fn claim_history_is_removed_with_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		let _ = PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim.clone(), 2);
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(2), claim.clone()));
+
+		assert!(!ClaimHistory::<Test>::contains_key(&claim));
+		assert_eq!(PoeModule::claim_history(vec![0, 1]), vec![]);
+	})
+}
+
+#[test]
+fn claim_history_of_unknown_claim_is_empty() {
+	new_test_ext_with_claims(vec![(vec![0, 1], 1)]).execute_with(|| {
+		assert_eq!(PoeModule::claim_history(vec![0, 1]), vec![(1, 0)]);
+		assert_eq!(PoeModule::claim_history(vec![2]), vec![]);
+		assert_eq!(PoeModule::claim_history(vec![0; 11]), vec![]);
+	})
+}
+
+#[test]
+fn offer_and_accept_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(1), claim.clone(), 2, Some(5)));
+		System::assert_last_event(
+			Event::<Test>::TransferOffered {
+				from: 1,
+				to: 2,
+				claim: claim.clone(),
+				expires_at: Some(5),
+			}
+			.into(),
+		);
+		// The claim is left to its owner until the offer is accepted.
+		assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(1));
+
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(2), claim.clone()));
+
+		assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(2));
+		assert!(!PendingTransfers::<Test>::contains_key(&claim));
+		assert_eq!(held(1), 0);
+		assert_eq!(held(2), 4);
+		System::assert_last_event(Event::<Test>::ClaimTransferred { from: 1, to: 2, claim }.into());
+	})
+}
+
+#[test]
+fn offer_claim_failed_with_wrong_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_noop!(
+			PoeModule::offer_claim(RuntimeOrigin::signed(2), claim.clone(), 2, None),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::offer_claim(RuntimeOrigin::signed(1), claim.clone(), 2, Some(1)),
+			Error::<Test>::InvalidExpiry
+		);
+	})
+}
+
+#[test]
+fn accept_claim_failed_with_wrong_recipient() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_noop!(
+			PoeModule::accept_claim(RuntimeOrigin::signed(2), claim.clone()),
+			Error::<Test>::OfferNotExist
+		);
+
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(1), claim.clone(), 2, None));
+		assert_noop!(
+			PoeModule::accept_claim(RuntimeOrigin::signed(3), claim.clone()),
+			Error::<Test>::NotOfferRecipient
+		);
+	})
+}
+
+#[test]
+fn accept_claim_failed_when_offer_expired() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(1), claim.clone(), 2, Some(3)));
+
+		System::set_block_number(3);
+		assert_noop!(
+			PoeModule::accept_claim(RuntimeOrigin::signed(2), claim.clone()),
+			Error::<Test>::OfferExpired
+		);
+	})
+}
+
+#[test]
+fn cancel_offer_works() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		// The owner withdraws the offer.
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(1), claim.clone(), 2, None));
+		assert_ok!(PoeModule::cancel_offer(RuntimeOrigin::signed(1), claim.clone()));
+		System::assert_last_event(Event::<Test>::OfferCancelled { claim: claim.clone() }.into());
+		assert_noop!(
+			PoeModule::accept_claim(RuntimeOrigin::signed(2), claim.clone()),
+			Error::<Test>::OfferNotExist
+		);
+
+		// The recipient declines the offer.
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(1), claim.clone(), 2, None));
+		assert_noop!(
+			PoeModule::cancel_offer(RuntimeOrigin::signed(3), claim.clone()),
+			Error::<Test>::NotOfferParty
+		);
+		assert_ok!(PoeModule::cancel_offer(RuntimeOrigin::signed(2), claim.clone()));
+		assert!(!PendingTransfers::<Test>::contains_key(&claim));
+	})
+}
+
+#[test]
+fn offers_are_dropped_with_transfer_or_revocation() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(1), claim.clone(), 2, None));
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim.clone(), 3));
+		assert!(!PendingTransfers::<Test>::contains_key(&claim));
+
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(3), claim.clone(), 2, None));
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(3), claim.clone()));
+		assert!(!PendingTransfers::<Test>::contains_key(&claim));
+	})
+}
+
+#[test]
+fn create_claims_works() {
+	new_test_ext().execute_with(|| {
+		let claims: Vec<BoundedVec<u8, _>> = vec![
+			BoundedVec::try_from(vec![0, 1]).unwrap(),
+			BoundedVec::try_from(vec![2]).unwrap(),
+			BoundedVec::try_from(vec![3, 4, 5]).unwrap(),
+		];
+
+		assert_ok!(PoeModule::create_claims(RuntimeOrigin::signed(1), claims.clone()));
+
+		for claim in claims {
+			assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(1));
+			System::assert_has_event(Event::<Test>::ClaimCreated(1, claim).into());
+		}
+		assert_eq!(held(1), 12);
+	})
+}
+
+#[test]
+fn create_claims_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![2]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(2), claim.clone());
+
+		assert_noop!(
+			PoeModule::create_claims(
+				RuntimeOrigin::signed(1),
+				vec![BoundedVec::try_from(vec![0, 1]).unwrap(), claim]
+			),
+			Error::<Test>::ProofAlreadyExist
+		);
+	})
+}
+
+#[test]
+fn create_claims_failed_with_too_many_claims() {
+	new_test_ext().execute_with(|| {
+		let claims = (0..4).map(|i| BoundedVec::try_from(vec![i]).unwrap()).collect();
+
+		assert_noop!(
+			PoeModule::create_claims(RuntimeOrigin::signed(1), claims),
+			Error::<Test>::TooManyClaims
+		);
+	})
+}
+
+#[test]
+fn revoke_claims_works() {
+	new_test_ext().execute_with(|| {
+		let claims: Vec<BoundedVec<u8, _>> =
+			(0..3).map(|i| BoundedVec::try_from(vec![i]).unwrap()).collect();
+		let _ = PoeModule::create_claims(RuntimeOrigin::signed(1), claims.clone());
+
+		assert_ok!(PoeModule::revoke_claims(RuntimeOrigin::signed(1), claims.clone()));
+
+		for claim in claims {
+			assert_eq!(Proofs::<Test>::get(&claim), None);
+			System::assert_has_event(Event::<Test>::ClaimRevoked(1, claim).into());
+		}
+		assert_eq!(held(1), 0);
+	})
+}
+
+#[test]
+fn revoke_claims_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		let mine = BoundedVec::try_from(vec![0]).unwrap();
+		let theirs = BoundedVec::try_from(vec![1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), mine.clone());
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(2), theirs.clone());
+
+		assert_noop!(
+			PoeModule::revoke_claims(RuntimeOrigin::signed(1), vec![mine, theirs]),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn owned_claims_are_indexed() {
+	new_test_ext_with_claims(vec![(vec![9], 1)]).execute_with(|| {
+		let first = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let second = BoundedVec::try_from(vec![2]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), first.clone());
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), second.clone());
+		assert_eq!(PoeModule::claims_of(&1).len(), 3);
+
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), first.clone(), 2));
+		assert!(!OwnedClaims::<Test>::contains_key(1, &first));
+		assert_eq!(PoeModule::claims_of(&2), vec![first.clone()]);
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), second.clone()));
+		assert_eq!(PoeModule::claims_of(&1), vec![BoundedVec::try_from(vec![9]).unwrap()]);
+
+		System::set_block_number(2);
+		assert_ok!(PoeModule::create_claim_with_metadata(
+			RuntimeOrigin::signed(3),
+			second.clone(),
+			None,
+			None,
+			Some(3)
+		));
+		assert_eq!(PoeModule::claims_of(&3), vec![second]);
+		PoeModule::on_initialize(3);
+		assert_eq!(PoeModule::claims_of(&3), vec![]);
+	})
+}
+
+#[test]
+fn claim_info_works() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+
+		assert_eq!(PoeModule::claim_info(vec![0, 1]), Proofs::<Test>::get(&claim));
+		assert!(PoeModule::claim_info(vec![0, 1]).is_some());
+		assert_eq!(PoeModule::claim_info(vec![2]), None);
+		assert_eq!(PoeModule::claim_info(vec![0; 11]), None);
+	})
+}
+
+#[test]
+fn migrate_to_v2_works() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		let _ = OwnedClaims::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(1).put::<PoeModule>();
+
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert!(OwnedClaims::<Test>::contains_key(1, &claim));
+		assert_eq!(PoeModule::on_chain_storage_version(), 2);
+	})
+}
+
+#[test]
+fn approve_and_revoke_operator_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::approve_operator(RuntimeOrigin::signed(1), 2));
+		assert!(Operators::<Test>::contains_key(1, 2));
+		System::assert_last_event(Event::<Test>::OperatorApproved { owner: 1, operator: 2 }.into());
+		assert_noop!(
+			PoeModule::approve_operator(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::OperatorAlreadyApproved
+		);
+
+		assert_ok!(PoeModule::revoke_operator(RuntimeOrigin::signed(1), 2));
+		assert!(!Operators::<Test>::contains_key(1, 2));
+		System::assert_last_event(Event::<Test>::OperatorRevoked { owner: 1, operator: 2 }.into());
+		assert_noop!(
+			PoeModule::revoke_operator(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::NotOperator
+		);
+	})
+}
+
+#[test]
+fn operator_can_revoke_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		assert_ok!(PoeModule::approve_operator(RuntimeOrigin::signed(1), 2));
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(2), claim.clone()));
+
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		// The deposit goes back to the owner, not to the operator.
+		assert_eq!(held(1), 0);
+		assert_eq!(Balances::free_balance(&1), 100);
+		System::assert_last_event(Event::<Test>::ClaimRevoked(1, claim).into());
+	})
+}
+
+#[test]
+fn operator_can_offer_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		assert_ok!(PoeModule::approve_operator(RuntimeOrigin::signed(1), 2));
+
+		assert_ok!(PoeModule::offer_claim(RuntimeOrigin::signed(2), claim.clone(), 3, None));
+		System::assert_last_event(
+			Event::<Test>::TransferOffered {
+				from: 1,
+				to: 3,
+				claim: claim.clone(),
+				expires_at: None,
+			}
+			.into(),
+		);
+
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(3), claim.clone()));
+
+		// The claim and its deposit move from the owner, not from the operator.
+		assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(3));
+		assert_eq!(held(1), 0);
+		assert_eq!(held(2), 0);
+		assert_eq!(held(3), 4);
+		System::assert_last_event(Event::<Test>::ClaimTransferred { from: 1, to: 3, claim }.into());
+	})
+}
+
+#[test]
+fn operator_can_transfer_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		assert_ok!(PoeModule::approve_operator(RuntimeOrigin::signed(1), 2));
+
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(2), claim.clone(), 3));
+
+		assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(3));
+		assert_eq!(held(3), 4);
+		System::assert_last_event(Event::<Test>::ClaimTransferred { from: 1, to: 3, claim }.into());
+	})
+}
+
+#[test]
+fn revoked_operator_cannot_manage_claims() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		assert_ok!(PoeModule::approve_operator(RuntimeOrigin::signed(1), 2));
+		assert_ok!(PoeModule::revoke_operator(RuntimeOrigin::signed(1), 2));
+
+		assert_noop!(
+			PoeModule::revoke_claim(RuntimeOrigin::signed(2), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::transfer_claim(RuntimeOrigin::signed(2), claim.clone(), 3),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::offer_claim(RuntimeOrigin::signed(2), claim.clone(), 3, None),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn operators_are_per_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+		// `2` operates the claims of `3`, not those of `1`.
+		assert_ok!(PoeModule::approve_operator(RuntimeOrigin::signed(3), 2));
+
+		assert_noop!(
+			PoeModule::revoke_claims(RuntimeOrigin::signed(2), vec![claim]),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn merkle_proofs_verify_every_leaf() {
+	for count in 1..=5u8 {
+		let leaves = (0..count).map(|i| vec![i]).collect::<Vec<_>>();
+		let root = merkle::merkle_root(&leaves).unwrap();
+
+		for (index, leaf) in leaves.iter().enumerate() {
+			let proof = merkle::merkle_proof(&leaves, index).unwrap();
+			assert!(merkle::verify_proof(&root, leaf, &proof));
+			assert!(!merkle::verify_proof(&root, &[count], &proof));
+		}
+		assert_eq!(merkle::merkle_proof(&leaves, count as usize), None);
+	}
+	assert_eq!(merkle::merkle_root::<Vec<u8>>(&[]), None);
+}
+
+#[test]
+fn anchor_root_works() {
+	new_test_ext().execute_with(|| {
+		let leaves: [&[u8]; 2] = [b"first", b"second"];
+		let root = merkle::merkle_root(&leaves).unwrap();
+
+		assert_ok!(PoeModule::anchor_root(RuntimeOrigin::signed(1), root));
+
+		assert_eq!(
+			Roots::<Test>::get(root),
+			Some(RootInfo { owner: 1, block_number: 1, deposit: 2 })
+		);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::RootDeposit.into(), &1), 2);
+		System::assert_last_event(Event::<Test>::RootAnchored { owner: 1, root }.into());
+		assert_noop!(
+			PoeModule::anchor_root(RuntimeOrigin::signed(2), root),
+			Error::<Test>::RootAlreadyAnchored
+		);
+	})
+}
+
+#[test]
+fn anchor_root_failed_with_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::anchor_root(RuntimeOrigin::signed(4), H256::repeat_byte(1)),
+			Error::<Test>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn remove_root_releases_deposit() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+		assert_ok!(PoeModule::anchor_root(RuntimeOrigin::signed(1), root));
+
+		assert_noop!(
+			PoeModule::remove_root(RuntimeOrigin::signed(2), root),
+			Error::<Test>::NotRootOwner
+		);
+		assert_ok!(PoeModule::remove_root(RuntimeOrigin::signed(1), root));
+
+		assert!(!Roots::<Test>::contains_key(root));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::RootDeposit.into(), &1), 0);
+		assert_eq!(Balances::balance(&1), 100);
+		System::assert_last_event(Event::<Test>::RootRemoved { owner: 1, root }.into());
+		assert_noop!(
+			PoeModule::remove_root(RuntimeOrigin::signed(1), root),
+			Error::<Test>::RootNotExist
+		);
+	})
+}
+
+#[test]
+fn verify_inclusion_requires_anchored_root() {
+	new_test_ext().execute_with(|| {
+		let leaves: [&[u8]; 3] = [b"first", b"second", b"third"];
+		let root = merkle::merkle_root(&leaves).unwrap();
+		let proof = merkle::merkle_proof(&leaves, 1).unwrap();
+		assert!(!PoeModule::verify_inclusion(root, b"second", &proof));
+
+		assert_ok!(PoeModule::anchor_root(RuntimeOrigin::signed(1), root));
+
+		assert!(PoeModule::verify_inclusion(root, b"second", &proof));
+		assert!(!PoeModule::verify_inclusion(root, b"fourth", &proof));
+	})
+}
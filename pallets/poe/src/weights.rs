@@ -1,34 +1,7 @@
-
-//! Autogenerated weights for pallet_poe
+//! Weights for pallet_poe
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-10-23, STEPS: `20`, REPEAT: `10`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Kaichaos-MacBook-Pro-720.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ./target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --execution
-// wasm
-// --wasm-execution
-// compiled
-// --pallet
-// pallet_poe
-// --extrinsic
-// *
-// --steps
-// 20
-// --repeat
-// 10
-// --output
-// ./pallets/poe/src/weights.rs
-// --template
-// .maintain/frame-weight-template.hbs
+//! These are estimates counted from the storage accesses of each call, not benchmark results.
+//! Regenerate this file with `benchmark pallet` (see the README) before relying on them.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -42,97 +15,490 @@ pub trait WeightInfo {
 	fn create_claim(d: u32, ) -> Weight;
 	fn revoke_claim(d: u32, ) -> Weight;
 	fn transfer_claim(d: u32, ) -> Weight;
+	fn create_claim_with_metadata(d: u32, m: u32, c: u32, ) -> Weight;
+	fn on_initialize(e: u32, ) -> Weight;
+	fn offer_claim(d: u32, ) -> Weight;
+	fn accept_claim(d: u32, ) -> Weight;
+	fn cancel_offer(d: u32, ) -> Weight;
+	fn create_claims(n: u32, d: u32, ) -> Weight;
+	fn revoke_claims(n: u32, d: u32, ) -> Weight;
+	fn approve_operator() -> Weight;
+	fn revoke_operator() -> Weight;
+	fn anchor_root() -> Weight;
+	fn remove_root() -> Weight;
 }
 
 /// Weights for pallet_poe using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: `PoeModule::Proofs` (r:1 w:1)
-	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:0 w:1)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:1)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
 	/// The range of component `d` is `[0, 10]`.
 	fn create_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `6`
-		//  Estimated: `3528`
-		// Minimum execution time: 9_000_000 picoseconds.
-		Weight::from_parts(10_000_955, 3528)
-			// Standard Error: 4_590
-			.saturating_add(Weight::from_parts(4_013, 0).saturating_mul(d.into()))
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		Weight::from_parts(28_000_000, 3617)
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `PoeModule::Proofs` (r:1 w:1)
-	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Expiries` (r:1 w:1)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:1)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:0 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:1 w:1)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
 	/// The range of component `d` is `[0, 10]`.
 	fn revoke_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `85 + d * (1 ±0)`
-		//  Estimated: `3528`
-		// Minimum execution time: 10_000_000 picoseconds.
-		Weight::from_parts(10_548_865, 3528)
-			// Standard Error: 15_304
-			.saturating_add(Weight::from_parts(27_459, 0).saturating_mul(d.into()))
+		Weight::from_parts(33_000_000, 3617)
+			.saturating_add(Weight::from_parts(27_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `PoeModule::Proofs` (r:1 w:1)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:1 w:0)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:2)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	fn transfer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(46_000_000, 6140)
+			.saturating_add(Weight::from_parts(31_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `PoeModule::Proofs` (r:1 w:1)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Expiries` (r:1 w:1)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:0 w:1)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:1)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	/// The range of component `m` is `[0, 16]`.
+	/// The range of component `c` is `[0, 8]`.
+	fn create_claim_with_metadata(d: u32, m: u32, c: u32, ) -> Weight {
+		Weight::from_parts(32_000_000, 3617)
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `PoeModule::Expiries` (r:1 w:1)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Proofs` (r:2 w:2)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:2 w:2)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:0 w:2)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:2)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:2)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `e` is `[0, 2]`.
+	fn on_initialize(e: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 3550)
+			.saturating_add(Weight::from_parts(28_000_000, 0).saturating_mul(e.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(e.into()))
+	}
+	/// Storage: `PoeModule::Proofs` (r:1 w:0)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	fn offer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 3617)
+			.saturating_add(Weight::from_parts(9_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `PoeModule::PendingTransfers` (r:1 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
 	/// Storage: `PoeModule::Proofs` (r:1 w:1)
-	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:1 w:0)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:2)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
 	/// The range of component `d` is `[0, 10]`.
-	fn transfer_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `85 + d * (1 ±0)`
-		//  Estimated: `3528`
-		// Minimum execution time: 7_000_000 picoseconds.
-		Weight::from_parts(7_716_488, 3528)
-			// Standard Error: 10_996
-			.saturating_add(Weight::from_parts(31_472, 0).saturating_mul(d.into()))
+	fn accept_claim(d: u32, ) -> Weight {
+		Weight::from_parts(52_000_000, 6140)
+			.saturating_add(Weight::from_parts(35_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `PoeModule::PendingTransfers` (r:1 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Proofs` (r:1 w:0)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	fn cancel_offer(d: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 3617)
+			.saturating_add(Weight::from_parts(13_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PoeModule::Proofs` (r:3 w:3)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:3 w:3)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:0 w:3)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:3)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 3]`.
+	/// The range of component `d` is `[4, 10]`.
+	fn create_claims(n: u32, d: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 3550)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(38_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(n.into()))
+	}
+	/// Storage: `PoeModule::Proofs` (r:3 w:3)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Expiries` (r:3 w:3)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:3 w:3)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:0 w:3)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:3)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:3)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 3]`.
+	/// The range of component `d` is `[4, 10]`.
+	fn revoke_claims(n: u32, d: u32, ) -> Weight {
+		Weight::from_parts(11_000_000, 3550)
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(53_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(n.into()))
+	}
+	/// Storage: `PoeModule::Operators` (r:1 w:1)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn approve_operator() -> Weight {
+		Weight::from_parts(13_000_000, 3545)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `PoeModule::Operators` (r:1 w:1)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn revoke_operator() -> Weight {
+		Weight::from_parts(14_000_000, 3545)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PoeModule::Roots` (r:1 w:1)
+	/// Proof: `PoeModule::Roots` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	fn anchor_root() -> Weight {
+		Weight::from_parts(25_000_000, 3565)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `PoeModule::Roots` (r:1 w:1)
+	/// Proof: `PoeModule::Roots` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	fn remove_root() -> Weight {
+		Weight::from_parts(25_000_000, 3565)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	/// Storage: `PoeModule::Proofs` (r:1 w:1)
-	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:0 w:1)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:1)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
 	/// The range of component `d` is `[0, 10]`.
 	fn create_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `6`
-		//  Estimated: `3528`
-		// Minimum execution time: 9_000_000 picoseconds.
-		Weight::from_parts(10_000_955, 3528)
-			// Standard Error: 4_590
-			.saturating_add(Weight::from_parts(4_013, 0).saturating_mul(d.into()))
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(28_000_000, 3617)
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `PoeModule::Proofs` (r:1 w:1)
-	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Expiries` (r:1 w:1)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:1)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:0 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:1 w:1)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
 	/// The range of component `d` is `[0, 10]`.
 	fn revoke_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `85 + d * (1 ±0)`
-		//  Estimated: `3528`
-		// Minimum execution time: 10_000_000 picoseconds.
-		Weight::from_parts(10_548_865, 3528)
-			// Standard Error: 15_304
-			.saturating_add(Weight::from_parts(27_459, 0).saturating_mul(d.into()))
+		Weight::from_parts(33_000_000, 3617)
+			.saturating_add(Weight::from_parts(27_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `PoeModule::Proofs` (r:1 w:1)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:1 w:0)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:2)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	fn transfer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(46_000_000, 6140)
+			.saturating_add(Weight::from_parts(31_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `PoeModule::Proofs` (r:1 w:1)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Expiries` (r:1 w:1)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:0 w:1)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:1)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	/// The range of component `m` is `[0, 16]`.
+	/// The range of component `c` is `[0, 8]`.
+	fn create_claim_with_metadata(d: u32, m: u32, c: u32, ) -> Weight {
+		Weight::from_parts(32_000_000, 3617)
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(4_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `PoeModule::Expiries` (r:1 w:1)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Proofs` (r:2 w:2)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:2 w:2)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:0 w:2)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:2)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:2)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `e` is `[0, 2]`.
+	fn on_initialize(e: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 3550)
+			.saturating_add(Weight::from_parts(28_000_000, 0).saturating_mul(e.into()))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(e.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(e.into()))
 	}
+	/// Storage: `PoeModule::Proofs` (r:1 w:0)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	fn offer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 3617)
+			.saturating_add(Weight::from_parts(9_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PoeModule::PendingTransfers` (r:1 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
 	/// Storage: `PoeModule::Proofs` (r:1 w:1)
-	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:1 w:0)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:1 w:1)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:2)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
 	/// The range of component `d` is `[0, 10]`.
-	fn transfer_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `85 + d * (1 ±0)`
-		//  Estimated: `3528`
-		// Minimum execution time: 7_000_000 picoseconds.
-		Weight::from_parts(7_716_488, 3528)
-			// Standard Error: 10_996
-			.saturating_add(Weight::from_parts(31_472, 0).saturating_mul(d.into()))
+	fn accept_claim(d: u32, ) -> Weight {
+		Weight::from_parts(52_000_000, 6140)
+			.saturating_add(Weight::from_parts(35_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `PoeModule::PendingTransfers` (r:1 w:1)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Proofs` (r:1 w:0)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// The range of component `d` is `[0, 10]`.
+	fn cancel_offer(d: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 3617)
+			.saturating_add(Weight::from_parts(13_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PoeModule::Proofs` (r:3 w:3)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:3 w:3)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:0 w:3)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:3)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 3]`.
+	/// The range of component `d` is `[4, 10]`.
+	fn create_claims(n: u32, d: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 3550)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(38_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(n.into()))
+	}
+	/// Storage: `PoeModule::Proofs` (r:3 w:3)
+	/// Proof: `PoeModule::Proofs` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Expiries` (r:3 w:3)
+	/// Proof: `PoeModule::Expiries` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Deposits` (r:3 w:3)
+	/// Proof: `PoeModule::Deposits` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::ClaimHistory` (r:0 w:3)
+	/// Proof: `PoeModule::ClaimHistory` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::PendingTransfers` (r:0 w:3)
+	/// Proof: `PoeModule::PendingTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::OwnedClaims` (r:0 w:3)
+	/// Proof: `PoeModule::OwnedClaims` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `PoeModule::Operators` (r:1 w:0)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 3]`.
+	/// The range of component `d` is `[4, 10]`.
+	fn revoke_claims(n: u32, d: u32, ) -> Weight {
+		Weight::from_parts(11_000_000, 3550)
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(53_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(n.into()))
+	}
+	/// Storage: `PoeModule::Operators` (r:1 w:1)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn approve_operator() -> Weight {
+		Weight::from_parts(13_000_000, 3545)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `PoeModule::Operators` (r:1 w:1)
+	/// Proof: `PoeModule::Operators` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn revoke_operator() -> Weight {
+		Weight::from_parts(14_000_000, 3545)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PoeModule::Roots` (r:1 w:1)
+	/// Proof: `PoeModule::Roots` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	fn anchor_root() -> Weight {
+		Weight::from_parts(25_000_000, 3565)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `PoeModule::Roots` (r:1 w:1)
+	/// Proof: `PoeModule::Roots` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	fn remove_root() -> Weight {
+		Weight::from_parts(25_000_000, 3565)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
@@ -12,8 +12,11 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	traits::{
+		AccountIdConversion, BlakeTwo256, Block as BlockT, Extrinsic as ExtrinsicT,
+		IdentifyAccount, NumberFor, One, SaturatedConversion, Verify,
+	},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -21,7 +24,13 @@ use sp_std::prelude::*;
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
-use frame_support::genesis_builder_helper::{build_config, create_default_config};
+use codec::Encode;
+use frame_support::{
+	genesis_builder_helper::{build_config, create_default_config},
+	traits::{Currency, OnUnbalanced},
+	PalletId,
+};
+use frame_system::EnsureRoot;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, derive_impl, parameter_types,
@@ -48,6 +57,9 @@ pub use sp_runtime::{Perbill, Permill};
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Import the proof of existence pallet.
+pub use pallet_poe;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -226,7 +238,7 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = ();
 }
 
@@ -255,6 +267,142 @@ impl pallet_template::Config for Runtime {
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const ClaimDepositBase: Balance = 10 * EXISTENTIAL_DEPOSIT;
+	pub const ClaimDepositPerByte: Balance = EXISTENTIAL_DEPOSIT / 10;
+}
+
+/// Configure the pallet-poe in pallets/poe.
+impl pallet_poe::Config for Runtime {
+	type MaxClaimLength = ConstU32<512>;
+	// Only Blake2-256 digests of documents are notarized.
+	type ClaimHasher = pallet_poe::hashing::Blake2Hasher;
+	type MaxDescriptionLength = ConstU32<256>;
+	type MaxContentTypeLength = ConstU32<64>;
+	type MaxExpiriesPerBlock = ConstU32<64>;
+	type MaxHistoryLength = ConstU32<16>;
+	type MaxBatchSize = ConstU32<100>;
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ClaimDepositBase = ClaimDepositBase;
+	type ClaimDepositPerByte = ClaimDepositPerByte;
+	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
+
+parameter_types! {
+	pub const RevealPeriod: BlockNumber = 10 * MINUTES;
+	pub const CoinFlipPalletId: PalletId = PalletId(*b"py/cflip");
+	pub const CoinFlipTreasuryCut: Permill = Permill::from_percent(2);
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+}
+
+/// Deposits the treasury cut of coin flip stakes into the treasury account.
+pub struct ToTreasury;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for ToTreasury {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Runtime>) {
+		Balances::resolve_creating(&TreasuryAccount::get(), amount);
+	}
+}
+
+/// Configure the pallet-coinflip in pallets/coinflip.
+impl pallet_coinflip::Config for Runtime {
+	type Randomness = RandomnessCollectiveFlip;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	// Secrets are revealed from the next block on, for about ten minutes.
+	type RevealDelay = ConstU32<1>;
+	type RevealPeriod = RevealPeriod;
+	type MaxCommitsPerBlock = ConstU32<64>;
+	type PalletId = CoinFlipPalletId;
+	type TreasuryCut = CoinFlipTreasuryCut;
+	type Treasury = ToTreasury;
+	type LeaderboardSize = ConstU32<100>;
+	// At most 10 plays a minute. Loss limits apply per day, and raising one takes a day.
+	type MaxPlaysPerPeriod = ConstU32<10>;
+	type PlayPeriod = ConstU32<MINUTES>;
+	type LossLimitPeriod = ConstU32<DAYS>;
+	type LossLimitDelay = ConstU32<DAYS>;
+	type WeightInfo = pallet_coinflip::weights::SubstrateWeight<Runtime>;
+}
+
+/// Configure the pallet-ocw-signed in pallets/ocw-signed.
+impl pallet_ocw_signed::Config for Runtime {
+	type AuthorityId = pallet_ocw_signed::sr25519::AuthId;
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxPairs = ConstU32<16>;
+	type MaxPrices = ConstU32<64>;
+	type WeightInfo = pallet_ocw_signed::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const OcwUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+/// Configure the pallet-ocw-unsigned in pallets/ocw-unsigned.
+impl pallet_ocw_unsigned::Config for Runtime {
+	type AuthorityId = pallet_ocw_unsigned::sr25519::AuthId;
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxPairs = ConstU32<16>;
+	type MaxPrices = ConstU32<64>;
+	type MaxAuthorities = ConstU32<16>;
+	// A price every 3 blocks, which stays in the pool for as long.
+	type UnsignedInterval = ConstU32<3>;
+	type UnsignedPriority = OcwUnsignedPriority;
+	type UnsignedLongevity = ConstU64<3>;
+	type WeightInfo = pallet_ocw_unsigned::weights::SubstrateWeight<Runtime>;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Nonce,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		// Take the biggest period possible.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		// The block number is already `n + 1` while the offchain worker of block `n` runs.
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (account.into(), signature, extra)))
+	}
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime {
@@ -267,6 +415,14 @@ construct_runtime!(
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
+		// Proof of existence claims from pallets/poe.
+		PoeModule: pallet_poe,
+		RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip,
+		// Coin flip game from pallets/coinflip.
+		CoinFlipModule: pallet_coinflip,
+		// Price feeds from pallets/ocw-signed and pallets/ocw-unsigned.
+		OcwSignedModule: pallet_ocw_signed,
+		OcwUnsignedModule: pallet_ocw_unsigned,
 	}
 );
 
@@ -292,7 +448,10 @@ pub type SignedExtra = (
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+	pallet_poe::migrations::v1::MigrateToV1<Runtime>,
+	pallet_poe::migrations::v2::MigrateToV2<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -318,6 +477,10 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
 		[pallet_template, TemplateModule]
+		[pallet_poe, PoeModule]
+		[pallet_coinflip, CoinFlipModule]
+		[pallet_ocw_signed, OcwSignedModule]
+		[pallet_ocw_unsigned, OcwUnsignedModule]
 	);
 }
 
@@ -489,6 +652,68 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber> for Runtime {
+		fn claim_history(claim: Vec<u8>) -> Vec<(AccountId, BlockNumber)> {
+			PoeModule::claim_history(claim)
+		}
+
+		fn claims_of(account: AccountId) -> Vec<Vec<u8>> {
+			PoeModule::claims_of(&account).into_iter().map(|claim| claim.into_inner()).collect()
+		}
+
+		fn claim(claim: Vec<u8>) -> Option<pallet_poe_runtime_api::ClaimDetails<AccountId, BlockNumber>> {
+			PoeModule::claim_info(claim).map(|info| pallet_poe_runtime_api::ClaimDetails {
+				owner: info.owner,
+				block_number: info.block_number,
+				description: info.description.map(|description| description.into_inner()),
+				content_type: info.content_type.map(|content_type| content_type.into_inner()),
+				expires_at: info.expires_at,
+			})
+		}
+
+		fn verify_inclusion(root: sp_core::H256, leaf: Vec<u8>, proof: Vec<sp_core::H256>) -> bool {
+			PoeModule::verify_inclusion(root, &leaf, &proof)
+		}
+	}
+
+	impl pallet_coinflip_runtime_api::CoinFlipApi<Block, AccountId, Balance> for Runtime {
+		fn stats(account: AccountId) -> pallet_coinflip_runtime_api::PlayerStats<Balance> {
+			let stats = CoinFlipModule::stats(account);
+			pallet_coinflip_runtime_api::PlayerStats {
+				plays: stats.plays,
+				wins: stats.wins,
+				total_wagered: stats.total_wagered,
+				total_won: stats.total_won,
+			}
+		}
+
+		fn leaderboard() -> Vec<(AccountId, Balance)> {
+			CoinFlipModule::leaderboard().into_inner()
+		}
+	}
+
+	impl ocw_common_runtime_api::OcwSignedApi<Block, BlockNumber> for Runtime {
+		fn aggregates(
+			asset: Vec<u8>,
+			quote: Vec<u8>,
+			window: BlockNumber,
+		) -> ocw_common_runtime_api::PriceAggregates {
+			let pair = asset.try_into().ok().zip(quote.try_into().ok());
+			pair.map(|pair| OcwSignedModule::aggregates(&pair, window)).unwrap_or_default()
+		}
+	}
+
+	impl ocw_common_runtime_api::OcwUnsignedApi<Block, BlockNumber> for Runtime {
+		fn aggregates(
+			asset: Vec<u8>,
+			quote: Vec<u8>,
+			window: BlockNumber,
+		) -> ocw_common_runtime_api::PriceAggregates {
+			let pair = asset.try_into().ok().zip(quote.try_into().ok());
+			pair.map(|pair| OcwUnsignedModule::aggregates(&pair, window)).unwrap_or_default()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (